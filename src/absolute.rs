@@ -0,0 +1,632 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::{
+    error::ErrorKind,
+    explanation::{Explanation, Step},
+    rational::{RationalFunction, RationalInequality},
+    solution::{SolutionSet, Symbols},
+    types::{superscript, Number, Polynomial, Pretty, Sign},
+};
+
+/// an expression that may hold absolute values, such as `x^2 - 5|x| + 6`. it is kept
+/// as written so it can be shown back, except that any part without an absolute
+/// value in it is collected into a single [`RationalFunction`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expression {
+    Function(RationalFunction),
+    Abs(Box<Expression>),
+    Neg(Box<Expression>),
+    Add(Box<Expression>, Box<Expression>),
+    Mul(Box<Expression>, Box<Expression>),
+    Div(Box<Expression>, Box<Expression>),
+    Pow(Box<Expression>, u32),
+}
+
+/// where every condition holds, the expression equals `function`.
+#[derive(Debug, PartialEq, Clone)]
+struct Piece {
+    conditions: Vec<RationalInequality>,
+    function: RationalFunction,
+}
+
+/// the conditions of both lists, or `None` if one asks for the opposite sign of the
+/// same function as the other.
+fn merge(
+    conditions: &[RationalInequality],
+    more: &[RationalInequality],
+) -> Option<Vec<RationalInequality>> {
+    let mut merged = conditions.to_vec();
+    for condition in more {
+        match merged
+            .iter()
+            .find(|known| known.function() == condition.function())
+        {
+            Some(known) if known.sign() == condition.sign() => {}
+            Some(_) => return None,
+            None => merged.push(condition.clone()),
+        }
+    }
+    Some(merged)
+}
+
+/// every piece of `left` combined with every piece of `right` by `f`, where both
+/// can hold at once.
+fn combine(
+    left: Vec<Piece>,
+    right: Vec<Piece>,
+    f: impl Fn(RationalFunction, RationalFunction) -> Result<RationalFunction, ErrorKind>,
+) -> Result<Vec<Piece>, ErrorKind> {
+    let mut pieces = Vec::new();
+    for left in &left {
+        for right in &right {
+            if let Some(conditions) = merge(&left.conditions, &right.conditions) {
+                pieces.push(Piece {
+                    conditions,
+                    function: f(left.function.clone(), right.function.clone())?,
+                });
+            }
+        }
+    }
+    Ok(pieces)
+}
+
+/// every piece with its function changed by `f`.
+fn map(
+    pieces: Vec<Piece>,
+    f: impl Fn(RationalFunction) -> Result<RationalFunction, ErrorKind>,
+) -> Result<Vec<Piece>, ErrorKind> {
+    pieces
+        .into_iter()
+        .map(|piece| {
+            Ok(Piece {
+                conditions: piece.conditions,
+                function: f(piece.function)?,
+            })
+        })
+        .collect()
+}
+
+impl Expression {
+    /// the variable, or `""` for a constant.
+    pub fn character(&self) -> &str {
+        match self {
+            Self::Function(function) => function.character(),
+            Self::Abs(operand) | Self::Neg(operand) | Self::Pow(operand, _) => operand.character(),
+            Self::Add(left, right) | Self::Mul(left, right) | Self::Div(left, right) => {
+                match left.character() {
+                    "" => right.character(),
+                    character => character,
+                }
+            }
+        }
+    }
+    /// whether there is an absolute value anywhere in the expression.
+    pub fn has_absolute_value(&self) -> bool {
+        !matches!(self, Self::Function(_))
+    }
+    /// the rational function, if there is no absolute value left. fails with
+    /// [`ErrorKind::AbsoluteValue`] otherwise.
+    pub(crate) fn into_function(self) -> Result<RationalFunction, ErrorKind> {
+        match self {
+            Self::Function(function) => Ok(function),
+            _ => Err(ErrorKind::AbsoluteValue),
+        }
+    }
+    /// fail if `self` and `rhs` are in different variables.
+    pub(crate) fn check_character(&self, rhs: &Self) -> Result<(), ErrorKind> {
+        match (self.character(), rhs.character()) {
+            ("", _) | (_, "") => Ok(()),
+            (left, right) if left != right => Err(ErrorKind::InvalidCharacter {
+                expected: left.to_string(),
+                found: right.to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+    pub(crate) fn abs(self) -> Result<Self, ErrorKind> {
+        match self {
+            Self::Function(function) if function.is_constant() => {
+                match function.numerator().leading().signum() {
+                    Ordering::Less => Ok(Self::Function(function.negate()?)),
+                    _ => Ok(Self::Function(function)),
+                }
+            }
+            operand => Ok(Self::Abs(Box::new(operand))),
+        }
+    }
+    pub(crate) fn negate(self) -> Result<Self, ErrorKind> {
+        match self {
+            Self::Function(function) => Ok(Self::Function(function.negate()?)),
+            Self::Neg(operand) => Ok(*operand),
+            operand => Ok(Self::Neg(Box::new(operand))),
+        }
+    }
+    pub(crate) fn add(self, rhs: Self) -> Result<Self, ErrorKind> {
+        self.check_character(&rhs)?;
+        match (self, rhs) {
+            (Self::Function(left), Self::Function(right)) => Ok(Self::Function(left.add(right)?)),
+            (Self::Function(zero), other) | (other, Self::Function(zero)) if zero.is_zero() => {
+                Ok(other)
+            }
+            (left, right) => Ok(Self::Add(Box::new(left), Box::new(right))),
+        }
+    }
+    pub(crate) fn mul(self, rhs: Self) -> Result<Self, ErrorKind> {
+        self.check_character(&rhs)?;
+        match (self, rhs) {
+            (Self::Function(left), Self::Function(right)) => Ok(Self::Function(left.mul(right)?)),
+            (left, right) => Ok(Self::Mul(Box::new(left), Box::new(right))),
+        }
+    }
+    /// fails with [`ErrorKind::DivisionByZero`] if `rhs` is zero, as long as it has no
+    /// absolute value in it.
+    pub(crate) fn div(self, rhs: Self) -> Result<Self, ErrorKind> {
+        self.check_character(&rhs)?;
+        match (self, rhs) {
+            (Self::Function(left), Self::Function(right)) => {
+                Ok(Self::Function(left.mul(right.reciprocal()?)?))
+            }
+            (_, Self::Function(right)) if right.is_zero() => Err(ErrorKind::DivisionByZero),
+            (left, right) => Ok(Self::Div(Box::new(left), Box::new(right))),
+        }
+    }
+    pub(crate) fn pow(self, exponent: u32) -> Result<Self, ErrorKind> {
+        match (self, exponent) {
+            (Self::Function(base), exponent) => Ok(Self::Function(base.pow(exponent)?)),
+            (base, 1) => Ok(base),
+            (base, exponent) => Ok(Self::Pow(Box::new(base), exponent)),
+        }
+    }
+    /// the rational function the expression equals for each choice of sign of the
+    /// arguments of its absolute values, leaving out choices that cannot happen
+    /// together, and those where the expression divides by zero everywhere.
+    fn pieces(&self) -> Result<Vec<Piece>, ErrorKind> {
+        match self {
+            Self::Function(function) => Ok(vec![Piece {
+                conditions: Vec::new(),
+                function: function.clone(),
+            }]),
+            Self::Abs(argument) => {
+                let mut pieces = Vec::new();
+                for piece in argument.pieces()? {
+                    let function = piece.function;
+                    if function.is_constant() {
+                        // the sign is already known, so there is nothing to split
+                        let function = Self::Function(function).abs()?.into_function()?;
+                        pieces.push(Piece {
+                            conditions: piece.conditions,
+                            function,
+                        });
+                        continue;
+                    }
+                    let cases = [
+                        (Sign::Gte, function.clone()),
+                        (Sign::Lt, function.clone().negate()?),
+                    ];
+                    for (sign, value) in cases {
+                        let condition = RationalInequality::new(function.clone(), sign);
+                        if let Some(conditions) = merge(&piece.conditions, &[condition]) {
+                            pieces.push(Piece {
+                                conditions,
+                                function: value,
+                            });
+                        }
+                    }
+                }
+                Ok(pieces)
+            }
+            Self::Neg(operand) => map(operand.pieces()?, RationalFunction::negate),
+            Self::Pow(base, exponent) => map(base.pieces()?, |base| base.pow(*exponent)),
+            Self::Add(left, right) => {
+                combine(left.pieces()?, right.pieces()?, RationalFunction::add)
+            }
+            Self::Mul(left, right) => {
+                combine(left.pieces()?, right.pieces()?, RationalFunction::mul)
+            }
+            Self::Div(left, right) => {
+                let mut right = right.pieces()?;
+                right.retain(|piece| !piece.function.is_zero());
+                combine(left.pieces()?, right, |left, right| {
+                    left.mul(right.reciprocal()?)
+                })
+            }
+        }
+    }
+    /// the distinct arguments of the absolute values, outermost first.
+    pub fn arguments(&self) -> Vec<Expression> {
+        let mut arguments = Vec::new();
+        self.collect_arguments(&mut arguments);
+        arguments
+    }
+    fn collect_arguments(&self, arguments: &mut Vec<Expression>) {
+        match self {
+            Self::Function(_) => {}
+            Self::Abs(argument) => {
+                if !arguments.contains(argument) {
+                    arguments.push(argument.as_ref().clone());
+                }
+                argument.collect_arguments(arguments);
+            }
+            Self::Neg(operand) | Self::Pow(operand, _) => operand.collect_arguments(arguments),
+            Self::Add(left, right) | Self::Mul(left, right) | Self::Div(left, right) => {
+                left.collect_arguments(arguments);
+                right.collect_arguments(arguments);
+            }
+        }
+    }
+    /// how tightly the expression holds together when written out: 0 for a sum, 1 for
+    /// a product or a quotient, 2 for a power and 3 for a single number, variable or
+    /// absolute value.
+    pub(crate) fn precedence(&self) -> u8 {
+        match self {
+            Self::Function(function) => {
+                let terms: Vec<(Number, u32)> = function.numerator().terms().collect();
+                match terms.as_slice() {
+                    [(coefficient, _), ..] if coefficient.signum() == Ordering::Less => 0,
+                    [_, _, ..] if function.is_polynomial() => 0,
+                    [(coefficient, power)]
+                        if function.is_polynomial()
+                            && (*coefficient == Number::from(1)
+                                || (*power == 0 && coefficient.denominator() == 1)) =>
+                    {
+                        3
+                    }
+                    _ => 1,
+                }
+            }
+            Self::Neg(_) | Self::Add(..) => 0,
+            Self::Mul(..) | Self::Div(..) => 1,
+            Self::Pow(..) => 2,
+            Self::Abs(_) => 3,
+        }
+    }
+    /// whether the expression is written with a minus sign in front, and what
+    /// follows it, so that `a + (-b)` can be written `a - b`.
+    pub(crate) fn split_sign(&self) -> (bool, Self) {
+        match self {
+            Self::Neg(operand) => (true, operand.as_ref().clone()),
+            Self::Function(function)
+                if function.numerator().leading().signum() == Ordering::Less =>
+            {
+                match function.clone().negate() {
+                    Ok(negated) => (true, Self::Function(negated)),
+                    Err(_) => (false, self.clone()),
+                }
+            }
+            _ => (false, self.clone()),
+        }
+    }
+    /// write the expression, with powers as `x^2`, or as `x²` for
+    /// [`Symbols::Unicode`]. `bars` is set between the bars of an absolute value,
+    /// where a `|` right after an operand would close them.
+    fn fmt_with(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        symbols: Option<Symbols>,
+        bars: bool,
+    ) -> fmt::Result {
+        let operand = |f: &mut fmt::Formatter<'_>, operand: &Self, precedence: u8| {
+            if operand.precedence() < precedence {
+                write!(f, "(")?;
+                operand.fmt_with(f, symbols, false)?;
+                write!(f, ")")
+            } else {
+                operand.fmt_with(f, symbols, bars)
+            }
+        };
+        match self {
+            Self::Function(function) => match symbols {
+                None => write!(f, "{}", function),
+                Some(symbols) => write!(f, "{}", function.pretty(symbols)),
+            },
+            Self::Abs(argument) => {
+                write!(f, "|")?;
+                argument.fmt_with(f, symbols, true)?;
+                write!(f, "|")
+            }
+            Self::Neg(negated) => {
+                write!(f, "-")?;
+                operand(f, negated, 1)
+            }
+            Self::Add(left, right) => {
+                left.fmt_with(f, symbols, bars)?;
+                match right.split_sign() {
+                    (true, right) => {
+                        write!(f, " - ")?;
+                        operand(f, &right, 1)
+                    }
+                    (false, right) => {
+                        write!(f, " + ")?;
+                        right.fmt_with(f, symbols, bars)
+                    }
+                }
+            }
+            Self::Mul(left, right) => {
+                operand(f, left, 1)?;
+                // `2|x|` and `2(x + 1)` read as products, but `|x|2` would not, and
+                // neither would `|2|x||`
+                let times = match right.as_ref() {
+                    Self::Function(_) => right.precedence() >= 2,
+                    Self::Abs(_) => bars,
+                    Self::Pow(base, _) => bars && matches!(base.as_ref(), Self::Abs(_)),
+                    _ => false,
+                };
+                if times {
+                    write!(f, "*")?;
+                }
+                operand(f, right, 2)
+            }
+            Self::Div(left, right) => {
+                operand(f, left, 1)?;
+                write!(f, "/")?;
+                operand(f, right, 2)
+            }
+            Self::Pow(base, exponent) => {
+                operand(f, base, 3)?;
+                match symbols {
+                    Some(Symbols::Unicode) => write!(f, "{}", superscript(*exponent)),
+                    _ => write!(f, "^{}", exponent),
+                }
+            }
+        }
+    }
+    /// the expression written with `symbols`: `|x² - 4|` or plain `|x^2 - 4|`.
+    pub fn pretty(&self, symbols: Symbols) -> Pretty<'_, Self> {
+        Pretty {
+            value: self,
+            symbols,
+        }
+    }
+}
+
+impl From<RationalFunction> for Expression {
+    fn from(function: RationalFunction) -> Self {
+        Self::Function(function)
+    }
+}
+impl From<Polynomial> for Expression {
+    fn from(polynomial: Polynomial) -> Self {
+        Self::Function(polynomial.into())
+    }
+}
+
+/// `x^2 - 5|x| + 6`.
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, None, false)
+    }
+}
+impl fmt::Display for Pretty<'_, Expression> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt_with(f, Some(self.symbols), false)
+    }
+}
+
+/// one choice of sign for the arguments of the absolute values: where every
+/// condition holds, the absolute values can be dropped to give `inequality`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Case {
+    conditions: Vec<RationalInequality>,
+    inequality: RationalInequality,
+}
+impl Case {
+    /// each argument `≥ 0` or `< 0`.
+    pub fn conditions(&self) -> &[RationalInequality] {
+        &self.conditions
+    }
+    pub fn inequality(&self) -> &RationalInequality {
+        &self.inequality
+    }
+    /// the solution of the inequality, restricted to where the conditions hold.
+    pub fn solve(&self) -> Result<SolutionSet, ErrorKind> {
+        let mut solution = self.inequality.solve()?;
+        for condition in &self.conditions {
+            solution = solution.intersection(&condition.solve()?);
+        }
+        Ok(solution)
+    }
+}
+
+/// `expression sign 0`, with every term moved to the left-hand side, where the
+/// expression may hold absolute values.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AbsoluteInequality {
+    expression: Expression,
+    sign: Sign,
+}
+impl AbsoluteInequality {
+    /// `expression sign 0`.
+    pub fn new(expression: Expression, sign: Sign) -> Self {
+        Self { expression, sign }
+    }
+    /// `left sign right`, with `right` moved over to the left-hand side. fails if the
+    /// sides are in different variables, or if a coefficient overflows.
+    pub fn from_sides(left: Expression, sign: Sign, right: Expression) -> Result<Self, ErrorKind> {
+        Self::from_expr((left, sign, right))
+    }
+    pub(crate) fn from_expr(input: (Expression, Sign, Expression)) -> Result<Self, ErrorKind> {
+        let (left, sign, right) = input;
+        Ok(Self {
+            expression: left.add(right.negate()?)?,
+            sign,
+        })
+    }
+    /// everything moved to the left-hand side.
+    pub fn expression(&self) -> &Expression {
+        &self.expression
+    }
+    pub fn sign(&self) -> &Sign {
+        &self.sign
+    }
+    /// the same inequality as a rational one, if there is no absolute value in it.
+    pub fn rational_inequality(&self) -> Option<RationalInequality> {
+        let function = self.expression.clone().into_function().ok()?;
+        Some(RationalInequality::new(function, self.sign.clone()))
+    }
+    /// the inequality split by the sign of each argument of an absolute value, with
+    /// the cases that cannot happen left out.
+    pub fn cases(&self) -> Result<Vec<Case>, ErrorKind> {
+        Ok(self
+            .expression
+            .pieces()?
+            .into_iter()
+            .map(|piece| Case {
+                conditions: piece.conditions,
+                inequality: RationalInequality::new(piece.function, self.sign.clone()),
+            })
+            .collect())
+    }
+    /// the values of the variable for which the inequality holds: the union of the
+    /// solutions of the cases.
+    pub fn solve(&self) -> Result<SolutionSet, ErrorKind> {
+        self.solve_with(&mut Vec::new())
+    }
+    /// the solution worked out step by step, starting from the normalized form.
+    pub fn explain(&self) -> Result<Explanation, ErrorKind> {
+        self.explain_with(Vec::new())
+    }
+    /// work out the solution of `left sign right`, recording every step.
+    pub(crate) fn explain_sides(
+        input: (Expression, Sign, Expression),
+    ) -> Result<Explanation, ErrorKind> {
+        match input {
+            (Expression::Function(left), sign, Expression::Function(right)) => {
+                RationalInequality::explain_sides((left, sign, right))
+            }
+            input => Self::from_expr(input)?.explain_with(Vec::new()),
+        }
+    }
+    fn explain_with(&self, mut steps: Vec<Step>) -> Result<Explanation, ErrorKind> {
+        if let Some(inequality) = self.rational_inequality() {
+            return inequality.explain_with(steps);
+        }
+        let solution = self.solve_with(&mut steps)?;
+        steps.push(Step::Answer(solution));
+        Ok(Explanation::new(steps))
+    }
+    /// the solution, with the steps taken to reach it pushed onto `steps`.
+    fn solve_with(&self, steps: &mut Vec<Step>) -> Result<SolutionSet, ErrorKind> {
+        if let Some(inequality) = self.rational_inequality() {
+            return inequality.solve_with(steps);
+        }
+        steps.push(Step::SplitCases {
+            arguments: self.expression.arguments(),
+        });
+        let mut solution = SolutionSet::empty(self.expression.character());
+        for case in self.cases()? {
+            let part = case.solve()?;
+            solution = solution.union(&part);
+            steps.push(Step::Case {
+                conditions: case.conditions,
+                inequality: case.inequality,
+                solution: part,
+            });
+        }
+        Ok(solution)
+    }
+    /// the normalized inequality written with `symbols`: `|x² - 4| - 3x ≥ 0`, or
+    /// `|x^2 - 4| - 3x >= 0` in ASCII.
+    pub fn pretty(&self, symbols: Symbols) -> Pretty<'_, Self> {
+        Pretty {
+            value: self,
+            symbols,
+        }
+    }
+}
+
+/// the normalized form `|x - 3| - 2 < 0`.
+impl fmt::Display for AbsoluteInequality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} 0", self.expression, self.sign)
+    }
+}
+impl fmt::Display for Pretty<'_, AbsoluteInequality> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inequality = self.value;
+        write!(f, "{} ", inequality.expression.pretty(self.symbols))?;
+        match self.symbols {
+            Symbols::Unicode => write!(f, "{}", inequality.sign)?,
+            Symbols::Ascii => write!(f, "{}", inequality.sign.ascii())?,
+        }
+        write!(f, " 0")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_absolute;
+
+    fn parse(input: &str) -> AbsoluteInequality {
+        parse_absolute(input).unwrap()
+    }
+
+    #[test]
+    fn display_absolute_inequality() {
+        assert_eq!(parse("x^2-5|x|+6<0").to_string(), "x^2 - 5|x| + 6 < 0");
+        assert_eq!(parse("||x|-1| < 1/2").to_string(), "||x| - 1| - 1/2 < 0");
+        assert_eq!(parse("|2*|x|| < 1").to_string(), "|2*|x|| - 1 < 0");
+        assert_eq!(
+            parse("|x-1|/|x+1|^2 >= 1")
+                .pretty(Symbols::Unicode)
+                .to_string(),
+            "|x - 1|/|x + 1|² - 1 ≥ 0"
+        );
+        for input in ["x^2 - 5|x| + 6 < 0", "|2*|x|| - 1 < 0", "-|x - 3| + 2 > 0"] {
+            assert_eq!(parse(input).to_string(), input);
+            assert_eq!(parse(&parse(input).to_string()), parse(input));
+        }
+    }
+
+    #[test]
+    fn split_into_cases() {
+        let inequality = parse("|x-3| < 2");
+        assert_eq!(
+            inequality.expression().arguments(),
+            [Polynomial::new("x", vec![-3, 1]).into()]
+        );
+        let cases: Vec<_> = inequality
+            .cases()
+            .unwrap()
+            .iter()
+            .map(|case| {
+                let conditions: Vec<_> = case.conditions().iter().map(|c| c.to_string()).collect();
+                (conditions, case.inequality().to_string())
+            })
+            .collect();
+        assert_eq!(
+            cases,
+            [
+                (vec!["x - 3 ≥ 0".to_string()], "x - 5 < 0".to_string()),
+                (vec!["x - 3 < 0".to_string()], "-x + 1 < 0".to_string()),
+            ]
+        );
+        assert!(parse("|x| + x^2 < 1").rational_inequality().is_none());
+        assert!(parse("x^2 < 1").rational_inequality().is_some());
+    }
+
+    #[test]
+    fn solve_absolute_inequality() {
+        let solve = |input| parse(input).solve().unwrap().to_string();
+        assert_eq!(solve("|x-3| < 2"), "1 < x < 5");
+        assert_eq!(solve("|x^2-4| >= 3x"), "x ≤ 1 OR x ≥ 4");
+        assert_eq!(solve("x^2-5|x|+6 < 0"), "-3 < x < -2 OR 2 < x < 3");
+        assert_eq!(solve("||x|-1| < 1/2"), "-3/2 < x < -1/2 OR 1/2 < x < 3/2");
+        assert_eq!(solve("x/(|x|+x) > 0"), "x > 0");
+        assert_eq!(solve("|x| < -1"), "no solution");
+        assert_eq!(solve("|x-1| >= 0"), "all real number");
+    }
+
+    #[test]
+    fn divide_by_zero() {
+        let x = || Expression::Abs(Box::new(Polynomial::new("x", vec![0, 1]).into()));
+        let zero = || Expression::from(Polynomial::new("x", vec![0]));
+        assert_eq!(x().div(zero()), Err(ErrorKind::DivisionByZero));
+        for input in ["|x|/0 > 0", "|x|/(x-x) > 0", "|x|/|0| > 0"] {
+            assert_eq!(
+                parse_absolute(input).map_err(|error| error.kind().clone()),
+                Err(ErrorKind::DivisionByZero)
+            );
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::fmt;
+
+use crate::{
+    absolute::{AbsoluteInequality, Expression},
+    error::ErrorKind,
+    explanation::{Explanation, Step},
+    solution::{SolutionSet, Symbols},
+    types::{Pretty, Sign},
+};
+
+/// one or more comparisons in a row, such as `-3 < x^2 - 4x < 5`, that must all
+/// hold: each side is compared with the one after it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ChainedInequality {
+    sides: Vec<Expression>,
+    signs: Vec<Sign>,
+    inequalities: Vec<AbsoluteInequality>,
+}
+impl ChainedInequality {
+    /// `left sign right` on its own. fails if the sides are in different variables,
+    /// or if a coefficient overflows.
+    pub fn from_sides(left: Expression, sign: Sign, right: Expression) -> Result<Self, ErrorKind> {
+        Self::from_expr((left, sign, right))
+    }
+    pub(crate) fn from_expr(input: (Expression, Sign, Expression)) -> Result<Self, ErrorKind> {
+        let (left, sign, right) = input;
+        let inequality = AbsoluteInequality::from_sides(left.clone(), sign.clone(), right.clone())?;
+        Ok(Self {
+            sides: vec![left, right],
+            signs: vec![sign],
+            inequalities: vec![inequality],
+        })
+    }
+    /// the chain carried on by comparing its last side with `right`. fails if
+    /// `right` is in another variable than the rest of the chain.
+    pub fn then(mut self, sign: Sign, right: Expression) -> Result<Self, ErrorKind> {
+        for side in &self.sides {
+            side.check_character(&right)?;
+        }
+        let left = self.sides[self.sides.len() - 1].clone();
+        let inequality = AbsoluteInequality::from_sides(left, sign.clone(), right.clone())?;
+        self.sides.push(right);
+        self.signs.push(sign);
+        self.inequalities.push(inequality);
+        Ok(self)
+    }
+    /// the sides as written, one more than there are signs.
+    pub fn sides(&self) -> &[Expression] {
+        &self.sides
+    }
+    pub fn signs(&self) -> &[Sign] {
+        &self.signs
+    }
+    /// each neighbouring pair of sides, with everything moved to the left-hand side.
+    pub fn inequalities(&self) -> &[AbsoluteInequality] {
+        &self.inequalities
+    }
+    /// the variable, or `""` if every side is a constant.
+    pub fn character(&self) -> &str {
+        self.sides
+            .iter()
+            .map(Expression::character)
+            .find(|character| !character.is_empty())
+            .unwrap_or("")
+    }
+    /// each neighbouring pair of sides as written.
+    fn comparisons(&self) -> impl Iterator<Item = (Expression, Sign, Expression)> + '_ {
+        self.signs.iter().enumerate().map(move |(i, sign)| {
+            (
+                self.sides[i].clone(),
+                sign.clone(),
+                self.sides[i + 1].clone(),
+            )
+        })
+    }
+    /// the values of the variable for which every comparison holds: the
+    /// intersection of their solutions.
+    pub fn solve(&self) -> Result<SolutionSet, ErrorKind> {
+        let mut solution = SolutionSet::all(self.character());
+        for inequality in &self.inequalities {
+            solution = solution.intersection(&inequality.solve()?);
+        }
+        Ok(solution)
+    }
+    /// the solution worked out step by step from the sides as written. a chain is
+    /// solved one comparison at a time.
+    pub fn explain(&self) -> Result<Explanation, ErrorKind> {
+        if let [_] = self.inequalities.as_slice() {
+            return AbsoluteInequality::explain_sides(self.comparisons().next().unwrap());
+        }
+        let mut steps = vec![Step::SplitChain {
+            comparisons: self.comparisons().collect(),
+        }];
+        let mut answer = SolutionSet::all(self.character());
+        for (left, sign, right) in self.comparisons() {
+            let explanation =
+                AbsoluteInequality::explain_sides((left.clone(), sign.clone(), right.clone()))?;
+            if let [work @ .., Step::Answer(solution)] = explanation.steps() {
+                steps.extend_from_slice(work);
+                answer = answer.intersection(solution);
+                steps.push(Step::Comparison {
+                    left,
+                    sign,
+                    right,
+                    solution: solution.clone(),
+                });
+            }
+        }
+        steps.push(Step::Answer(answer));
+        Ok(Explanation::new(steps))
+    }
+    /// the chain written with `symbols`: `-3 < x² - 4x < 5`, or `-3 < x^2 - 4x < 5`
+    /// in ASCII. a single comparison is written in its normalized form.
+    pub fn pretty(&self, symbols: Symbols) -> Pretty<'_, Self> {
+        Pretty {
+            value: self,
+            symbols,
+        }
+    }
+}
+
+/// the chain as written, `-3 < x^2 - 4x < 5`, or the normalized form
+/// `x^2 - 4x - 5 < 0` of a single comparison.
+impl fmt::Display for ChainedInequality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let [inequality] = self.inequalities.as_slice() {
+            return write!(f, "{}", inequality);
+        }
+        write!(f, "{}", self.sides[0])?;
+        for (sign, side) in self.signs.iter().zip(&self.sides[1..]) {
+            write!(f, " {} {}", sign, side)?;
+        }
+        Ok(())
+    }
+}
+impl fmt::Display for Pretty<'_, ChainedInequality> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let chain = self.value;
+        if let [inequality] = chain.inequalities.as_slice() {
+            return write!(f, "{}", inequality.pretty(self.symbols));
+        }
+        write!(f, "{}", chain.sides[0].pretty(self.symbols))?;
+        for (sign, side) in chain.signs.iter().zip(&chain.sides[1..]) {
+            match self.symbols {
+                Symbols::Unicode => write!(f, " {} ", sign)?,
+                Symbols::Ascii => write!(f, " {} ", sign.ascii())?,
+            }
+            write!(f, "{}", side.pretty(self.symbols))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parser::parse_chained,
+        types::{Number, Polynomial},
+    };
+
+    fn parse(input: &str) -> ChainedInequality {
+        parse_chained(input).unwrap()
+    }
+
+    #[test]
+    fn build_chained_inequality() {
+        let x = |coefficients| Expression::from(Polynomial::new("x", coefficients));
+        let constant = |c| Expression::from(Polynomial::constant(Number::from(c)));
+        let chain = ChainedInequality::from_sides(constant(-3), Sign::Lt, x(vec![0, -4, 1]))
+            .and_then(|chain| chain.then(Sign::Lt, constant(5)))
+            .unwrap();
+        assert_eq!(chain, parse("-3 < x^2-4x < 5"));
+        assert_eq!(chain.signs(), [Sign::Lt, Sign::Lt]);
+        assert_eq!(
+            chain
+                .inequalities()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["-x^2 + 4x - 3 < 0", "x^2 - 4x - 5 < 0"]
+        );
+        assert_eq!(
+            chain.then(Sign::Lt, Expression::from(Polynomial::new("y", vec![0, 1]))),
+            Err(ErrorKind::InvalidCharacter {
+                expected: "x".to_string(),
+                found: "y".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn display_chained_inequality() {
+        assert_eq!(parse("-3<x^2-4x<5").to_string(), "-3 < x^2 - 4x < 5");
+        assert_eq!(
+            parse("x-1 <= x^2 <= 2x+3")
+                .pretty(Symbols::Ascii)
+                .to_string(),
+            "x - 1 <= x^2 <= 2x + 3"
+        );
+        assert_eq!(
+            parse("0 < |x-1| < 2").pretty(Symbols::Unicode).to_string(),
+            "0 < |x - 1| < 2"
+        );
+        assert_eq!(parse("x^2 < 1").to_string(), "x^2 - 1 < 0");
+    }
+
+    #[test]
+    fn solve_chained_inequality() {
+        let solve = |input| parse(input).solve().unwrap().to_string();
+        assert_eq!(solve("-3 < x^2-4x < 5"), "-1 < x < 1 OR 3 < x < 5");
+        assert_eq!(solve("x-1 <= x^2 <= 2x+3"), "-1 ≤ x ≤ 3");
+        assert_eq!(solve("0 < |x-1| < 2"), "-1 < x < 1 OR 1 < x < 3");
+        assert_eq!(solve("1 < 2 < x"), "x > 2");
+        assert_eq!(solve("0 < x < 1 < x^2"), "no solution");
+    }
+}
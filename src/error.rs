@@ -1,30 +1,221 @@
-use nom::error::{ErrorKind, FromExternalError, ParseError};
+use std::fmt::{self, Write};
+
 use thiserror::Error;
 
-#[derive(Error, Debug, PartialEq)]
-pub enum Error<'a> {
-    #[error("{0} is not an inequality sign")]
+use crate::locale::{Locale, Localize};
+
+/// A byte range `start..end` into the original input.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+/// what the parser was looking for when it gave up.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Expected {
+    Operand,
+    InequalitySign,
+    Exponent,
+    ClosingParenthesis,
+    ClosingBar,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ErrorKind {
     InvalidIneqSign(String),
-    #[error("invalid quadratic")]
     InvalidQuadratic,
-    #[error("invalid character: expected {expected}, found {found}")]
-    InvalidCharacter { expected: String, found: String },
-    #[error("parser error: input: {0}, kind: {1:?}")]
-    Nom(&'a str, ErrorKind),
+    InvalidPolynomial,
+    AbsoluteValue,
+    InvalidCharacter {
+        expected: String,
+        found: String,
+    },
+    DivisionByZero,
+    NegativeSquareRoot,
+    Overflow,
+    DegreeTooHigh,
+    TooDeeplyNested,
+    TooManyAbsoluteValues,
+    UnexpectedEnd,
+    TrailingInput {
+        found: String,
+    },
+    Syntax {
+        expected: Option<Expected>,
+        after: Option<String>,
+    },
 }
-impl<'a> ParseError<&'a str> for Error<'a> {
-    fn from_error_kind(input: &'a str, kind: nom::error::ErrorKind) -> Self {
-        Error::Nom(input, kind)
+impl ErrorKind {
+    /// a name for the kind of error that stays the same in every language, for
+    /// programs to match on.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidIneqSign(_) => "invalid_inequality_sign",
+            Self::InvalidQuadratic => "invalid_quadratic",
+            Self::InvalidPolynomial => "invalid_polynomial",
+            Self::AbsoluteValue => "absolute_value",
+            Self::InvalidCharacter { .. } => "invalid_character",
+            Self::DivisionByZero => "division_by_zero",
+            Self::NegativeSquareRoot => "negative_square_root",
+            Self::Overflow => "overflow",
+            Self::DegreeTooHigh => "degree_too_high",
+            Self::TooDeeplyNested => "too_deeply_nested",
+            Self::TooManyAbsoluteValues => "too_many_absolute_values",
+            Self::UnexpectedEnd => "unexpected_end",
+            Self::TrailingInput { .. } => "trailing_input",
+            Self::Syntax { .. } => "syntax",
+        }
     }
+}
+impl std::error::Error for ErrorKind {}
+impl Localize for ErrorKind {
+    fn fmt_localized(&self, f: &mut fmt::Formatter<'_>, locale: Locale) -> fmt::Result {
+        locale.fmt_error(f, self)
+    }
+}
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_localized(f, Locale::English)
+    }
+}
 
-    fn append(_: &str, _: nom::error::ErrorKind, other: Self) -> Self {
-        other
+/// What went wrong, and where in the input it went wrong.
+#[derive(Error, Debug, PartialEq, Clone)]
+#[error("{kind}")]
+pub struct Error {
+    kind: ErrorKind,
+    span: Span,
+}
+impl Error {
+    pub fn new(kind: ErrorKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+    pub fn span(&self) -> Span {
+        self.span
+    }
+    /// render the line of `input` the error points at, with the offending part
+    /// underlined and the message next to it:
+    ///
+    /// ```text
+    /// x^2+>0
+    ///     ^ expected a number or variable after '+'
+    /// ```
+    pub fn render(&self, input: &str) -> String {
+        self.render_in(input, Locale::English)
+    }
+    /// like [`Error::render`], with the message in `locale`.
+    pub fn render_in(&self, input: &str, locale: Locale) -> String {
+        // the span may come from elsewhere, so never trust it to fit `input`
+        let floor = |i: usize| {
+            let mut i = i.min(input.len());
+            while !input.is_char_boundary(i) {
+                i -= 1;
+            }
+            i
+        };
+        let start = floor(self.span.start);
+        let end = floor(self.span.end).max(start);
+        let line_start = input[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[start..].find('\n').map_or(input.len(), |i| start + i);
+        let mut rendered = format!("{}\n", &input[line_start..line_end]);
+        // keep tabs so the caret lines up however wide the terminal shows them
+        for c in input[line_start..start].chars() {
+            rendered.push(if c == '\t' { '\t' } else { ' ' });
+        }
+        let width = input[start..end.min(line_end)].chars().count().max(1);
+        let _ = write!(
+            rendered,
+            "{} {}",
+            "^".repeat(width),
+            self.kind.localized(locale)
+        );
+        rendered
     }
 }
-impl<'a> FromExternalError<&str, Error<'a>> for Error<'a> {
-    fn from_external_error(_: &str, _: ErrorKind, e: Error<'a>) -> Self {
-        e
+
+impl Localize for Error {
+    fn fmt_localized(&self, f: &mut fmt::Formatter<'_>, locale: Locale) -> fmt::Result {
+        self.kind.fmt_localized(f, locale)
     }
 }
 
-pub type Result<'a, T> = std::result::Result<T, Error<'a>>;
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_error() {
+        let error = Error::new(
+            ErrorKind::Syntax {
+                expected: Some(Expected::Operand),
+                after: Some("+".to_string()),
+            },
+            Span::new(4, 5),
+        );
+        assert_eq!(
+            error.render("x^2+>0"),
+            "x^2+>0\n    ^ expected a number or variable after '+'"
+        );
+    }
+
+    #[test]
+    fn render_error_at_end_of_input() {
+        let error = Error::new(
+            ErrorKind::Syntax {
+                expected: Some(Expected::InequalitySign),
+                after: None,
+            },
+            Span::new(4, 4),
+        );
+        assert_eq!(
+            error.render("\tx^2"),
+            "\tx^2\n\t   ^ expected an inequality sign"
+        );
+    }
+
+    #[test]
+    fn render_wide_span() {
+        let error = Error::new(
+            ErrorKind::TrailingInput {
+                found: "≥1".to_string(),
+            },
+            Span::new(5, 9),
+        );
+        assert_eq!(
+            error.render("x^2>0≥1"),
+            "x^2>0≥1\n     ^^ unexpected input '≥1'"
+        );
+    }
+
+    #[test]
+    fn render_span_outside_input() {
+        let error = Error::new(ErrorKind::Overflow, Span::new(3, 100));
+        assert_eq!(error.render("x>√2"), "x>√2\n  ^^ number too large");
+    }
+
+    #[test]
+    fn render_error_in_korean() {
+        let error = Error::new(
+            ErrorKind::Syntax {
+                expected: Some(Expected::Operand),
+                after: Some("+".to_string()),
+            },
+            Span::new(4, 5),
+        );
+        assert_eq!(
+            error.render_in("x^2+>0", Locale::Korean),
+            "x^2+>0\n    ^ '+' 뒤에 수나 변수가 와야 합니다"
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::{
+    absolute::Expression,
+    locale::{Locale, Localize},
+    polynomial::Root,
+    rational::{RationalFunction, RationalInequality},
+    real::Real,
+    solution::SolutionSet,
+    surd::Surd,
+    types::{Number, Polynomial, Quadratic, Sign},
+};
+
+/// which part of the number line a quadratic with two roots is solved on.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Region {
+    Between,
+    Outside,
+}
+
+/// one stage of working out a solution.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Step {
+    /// `left sign right` rewritten as `left - (right) sign 0`.
+    MoveToOneSide {
+        left: RationalFunction,
+        sign: Sign,
+        right: RationalFunction,
+    },
+    /// like terms collected into `polynomial sign 0`.
+    CollectTerms {
+        polynomial: Polynomial,
+        sign: Sign,
+    },
+    /// both sides multiplied by -1 to make the leading coefficient positive.
+    FlipSign {
+        quadratic: Quadratic,
+        sign: Sign,
+    },
+    /// `c sign 0` with no variable left, which is either always or never true.
+    Constant {
+        c: Number,
+        sign: Sign,
+        holds: bool,
+    },
+    /// the discriminant `b^2 - 4ac`.
+    Discriminant {
+        a: Number,
+        b: Number,
+        c: Number,
+        d: Number,
+    },
+    /// the real roots, smaller first: none, a double root, or two roots.
+    Roots {
+        character: String,
+        roots: Vec<Surd>,
+    },
+    /// with a positive leading coefficient, `sign` holds between or outside two roots.
+    ChooseIntervals {
+        sign: Sign,
+        region: Region,
+        inclusive: bool,
+    },
+    /// with a positive leading coefficient and no two roots, the quadratic is never
+    /// negative, so `sign` holds everywhere or nowhere apart from a double root.
+    NeverNegative {
+        sign: Sign,
+        double_root: Option<Surd>,
+    },
+    /// the real roots of a polynomial of degree 3 or more, smaller first.
+    PolynomialRoots {
+        character: String,
+        roots: Vec<Root>,
+    },
+    /// everything over a common denominator, as `function sign 0`.
+    CommonDenominator {
+        function: RationalFunction,
+        sign: Sign,
+    },
+    /// the real roots of the denominator, where the function is undefined.
+    ExcludePoles {
+        character: String,
+        poles: Vec<Real>,
+    },
+    /// the real roots of the numerator that are not roots of the denominator.
+    NumeratorRoots {
+        character: String,
+        roots: Vec<Root>,
+    },
+    /// the sign of the left-hand side left of the first root, between each pair of
+    /// neighbouring roots and right of the last one.
+    SignChart {
+        roots: Vec<Real>,
+        signs: Vec<Ordering>,
+    },
+    /// `sign` holds where the sign chart shows it, and at the roots if `inclusive`.
+    ChooseSigns {
+        sign: Sign,
+        inclusive: bool,
+    },
+    /// the absolute values taken apart by the sign of each of `arguments`.
+    SplitCases {
+        arguments: Vec<Expression>,
+    },
+    /// where every condition holds, the inequality without absolute values, and its
+    /// solution there.
+    Case {
+        conditions: Vec<RationalInequality>,
+        inequality: RationalInequality,
+        solution: SolutionSet,
+    },
+    /// a chain of comparisons, each of which is solved on its own.
+    SplitChain {
+        comparisons: Vec<(Expression, Sign, Expression)>,
+    },
+    /// the solution of one comparison of a chain.
+    Comparison {
+        left: Expression,
+        sign: Sign,
+        right: Expression,
+        solution: SolutionSet,
+    },
+    Answer(SolutionSet),
+}
+
+impl Step {
+    /// `D = b^2 - 4ac = ... = d`, the same in every language.
+    pub(crate) fn fmt_discriminant(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Self::Discriminant { a, b, c, d } = self {
+            write!(
+                f,
+                "D = b^2 - 4ac = {}^2 - 4·{}·{} = {}",
+                Parenthesized(b),
+                Parenthesized(a),
+                Parenthesized(c),
+                d
+            )?;
+        }
+        Ok(())
+    }
+    /// `- | -2 | + | 1 | +`, the same in every language.
+    pub(crate) fn fmt_sign_chart(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Self::SignChart { roots, signs } = self {
+            for (i, sign) in signs.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " | {} | ", roots[i - 1])?;
+                }
+                match sign {
+                    Ordering::Less => write!(f, "-")?,
+                    Ordering::Equal => write!(f, "0")?,
+                    Ordering::Greater => write!(f, "+")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Localize for Step {
+    fn fmt_localized(&self, f: &mut fmt::Formatter<'_>, locale: Locale) -> fmt::Result {
+        locale.fmt_step(f, self)
+    }
+}
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_localized(f, Locale::English)
+    }
+}
+
+/// a number wrapped in parentheses if it is negative or a fraction, so it can be
+/// written into a product.
+struct Parenthesized<'a>(&'a Number);
+impl fmt::Display for Parenthesized<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.signum() == std::cmp::Ordering::Less || self.0.denominator() != 1 {
+            write!(f, "({})", self.0)
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+/// the worked solution of an inequality, step by step.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Explanation {
+    steps: Vec<Step>,
+}
+impl Explanation {
+    pub(crate) fn new(steps: Vec<Step>) -> Self {
+        Self { steps }
+    }
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+}
+
+/// one numbered step per line.
+impl Localize for Explanation {
+    fn fmt_localized(&self, f: &mut fmt::Formatter<'_>, locale: Locale) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}. {}", i + 1, step.localized(locale))?;
+        }
+        Ok(())
+    }
+}
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_localized(f, Locale::English)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_discriminant() {
+        let step = Step::Discriminant {
+            a: Number::from(1),
+            b: Number::from(3),
+            c: Number::from(-10),
+            d: Number::from(49),
+        };
+        assert_eq!(
+            step.to_string(),
+            "discriminant: D = b^2 - 4ac = 3^2 - 4·1·(-10) = 49"
+        );
+    }
+
+    #[test]
+    fn display_roots() {
+        let roots = |roots| Step::Roots {
+            character: "x".to_string(),
+            roots,
+        };
+        assert_eq!(roots(vec![]).to_string(), "D < 0, so there is no real root");
+        assert_eq!(
+            roots(vec![Surd::integer(-2)]).to_string(),
+            "D = 0, so there is a double root x = -2"
+        );
+        assert_eq!(
+            roots(vec![Surd::integer(-5), Surd::integer(2)]).to_string(),
+            "D > 0, so there are two roots x = -5 and x = 2"
+        );
+    }
+
+    #[test]
+    fn display_polynomial_roots() {
+        let roots = |multiplicity| Step::PolynomialRoots {
+            character: "x".to_string(),
+            roots: Polynomial::new("x", vec![0, -1, 1])
+                .pow(multiplicity)
+                .unwrap()
+                .real_roots()
+                .unwrap(),
+        };
+        assert_eq!(
+            roots(2).to_string(),
+            "find the real roots: x = 0 (double root), x = 1 (double root)"
+        );
+        assert_eq!(
+            roots(4).localized(Locale::Korean).to_string(),
+            "실근을 구합니다: x = 0 (4중근), x = 1 (4중근)"
+        );
+        assert_eq!(
+            Step::PolynomialRoots {
+                character: "x".to_string(),
+                roots: vec![]
+            }
+            .to_string(),
+            "there is no real root"
+        );
+    }
+
+    #[test]
+    fn display_step_in_korean() {
+        let step = Step::ChooseIntervals {
+            sign: Sign::Lte,
+            region: Region::Between,
+            inclusive: true,
+        };
+        assert_eq!(
+            step.localized(Locale::Korean).to_string(),
+            "이차식은 두 근의 사이에서 ≤ 0입니다 (근 포함)"
+        );
+    }
+}
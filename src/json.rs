@@ -0,0 +1,411 @@
+//! the JSON documents written by `--format json`. the fields of the first version
+//! keep their names, types and meaning; later additions only ever add keys.
+//!
+//! a solved inequality:
+//!
+//! * `ok`: `true`.
+//! * `inequality`: the problem, normalized.
+//!   * `variable`: the variable, or `""` if there is none.
+//!   * `a`, `b`, `c`: the coefficients of `x^2`, `x` and 1 as exact strings such as
+//!     `"-1/2"`. `null` unless the problem is a single quadratic inequality.
+//!   * `sign`: one of `<`, `<=`, `>`, `>=`. `null` for a chain of comparisons.
+//!   * `text`: the normalized problem, as printed.
+//!   * `degree`, `coefficients`: the degree and the coefficients of the numerator,
+//!     `coefficients[n]` being that of `x^n`. `null` with absolute values or a chain.
+//!   * `denominator`: the coefficients of the denominator, or `null` if there is none.
+//! * `discriminant`: an exact string, or `null` unless the problem is quadratic.
+//! * `roots`: the real roots of the numerator, smaller first, each a number with its
+//!   `multiplicity`. empty with absolute values or a chain.
+//! * `excluded`: the numbers where the denominator is 0, left out of the answer.
+//! * `cases`: with absolute values, the `conditions` and the `inequality` of each
+//!   case as text; `null` otherwise.
+//! * `chain`: for a chain of comparisons, the `inequality`, `discriminant`, `roots`,
+//!   `excluded` and `cases` of each comparison as above; `null` otherwise.
+//! * `solution`: the answer as `text` in the chosen language, and as `intervals`,
+//!   each with a `lower` and an `upper` bound that is `null` if there is none, or a
+//!   number that is `closed` if it belongs to the interval.
+//!
+//! a number is `{"exact": "(-3 - √5)/2", "approx": -2.618..., "approximate": false}`.
+//! a root with no exact form has `"approximate": true`, and `exact` is then the
+//! rounded value as printed, such as `"≈1.259921"`.
+//!
+//! an input that could not be solved is `{"ok": false, "error": {"code": ...,
+//! "message": ..., "span": {"start": ..., "end": ...}}}`, with the span in bytes.
+
+use serde_json::{json, Value};
+
+use crate::{
+    absolute::AbsoluteInequality,
+    chained::ChainedInequality,
+    error::Error,
+    locale::{Locale, Localize},
+    rational::RationalInequality,
+    real::Real,
+    solution::{Bound, SolutionSet},
+    types::{Polynomial, Quadratic, Sign},
+};
+
+/// `{"exact": "(-3 - √5)/2", "approx": -2.618..., "approximate": false}`.
+fn real(value: &Real) -> Value {
+    json!({
+        "exact": value.to_string(),
+        "approx": value.to_f64(),
+        "approximate": !value.is_exact(),
+    })
+}
+
+/// `null` for no bound, or the value and whether it belongs to the interval.
+fn bound(bound: &Bound) -> Value {
+    match bound {
+        Bound::Unbounded => Value::Null,
+        Bound::Open(value) | Bound::Closed(value) => {
+            let mut document = real(value);
+            document["closed"] = bound.is_closed().into();
+            document
+        }
+    }
+}
+
+/// the sign as typed on a keyboard.
+fn sign(sign: &Sign) -> &'static str {
+    match sign {
+        Sign::Lt => "<",
+        Sign::Lte => "<=",
+        Sign::Gt => ">",
+        Sign::Gte => ">=",
+    }
+}
+
+/// the coefficients as exact strings, `[n]` being the coefficient of `x^n`.
+fn coefficients(polynomial: &Polynomial) -> Vec<String> {
+    (0..=polynomial.degree())
+        .map(|n| polynomial.coefficient(n).to_string())
+        .collect()
+}
+
+/// the normalized form of an inequality without absolute values: its
+/// coefficients, the discriminant (`null` unless it is quadratic), the roots with
+/// their multiplicities and the zeros of the denominator.
+fn rational(inequality: &RationalInequality) -> Value {
+    let function = inequality.function();
+    let polynomial = function.numerator();
+    let degree = polynomial.degree();
+    let quadratic = |n| match (function.is_polynomial(), degree) {
+        (true, 0..=2) => Some(polynomial.coefficient(n).to_string()),
+        _ => None,
+    };
+    let discriminant = match (function.is_polynomial(), degree) {
+        (true, 2) => Quadratic::from_polynomial(polynomial.clone())
+            .and_then(|quadratic| quadratic.discriminant())
+            .ok(),
+        _ => None,
+    };
+    let denominator = Some(function.denominator())
+        .filter(|_| !function.is_polynomial())
+        .map(coefficients);
+    let excluded: Vec<Value> = function
+        .poles()
+        .unwrap_or_default()
+        .iter()
+        .map(real)
+        .collect();
+    let roots: Vec<Value> = function
+        .zeros()
+        .unwrap_or_default()
+        .iter()
+        .map(|root| {
+            let mut document = real(&root.value());
+            document["multiplicity"] = root.multiplicity().into();
+            document
+        })
+        .collect();
+    json!({
+        "inequality": {
+            "degree": degree,
+            "coefficients": coefficients(polynomial),
+            "denominator": denominator,
+            "a": quadratic(2),
+            "b": quadratic(1),
+            "c": quadratic(0),
+        },
+        "discriminant": discriminant.map(|d| d.to_string()),
+        "roots": roots,
+        "excluded": excluded,
+        "cases": null,
+    })
+}
+
+/// an inequality with absolute values, which has no coefficients of its own: the
+/// conditions and the inequality of each case instead.
+fn absolute(inequality: &AbsoluteInequality) -> Value {
+    let cases: Vec<Value> = inequality
+        .cases()
+        .unwrap_or_default()
+        .iter()
+        .map(|case| {
+            let conditions: Vec<String> =
+                case.conditions().iter().map(ToString::to_string).collect();
+            json!({
+                "conditions": conditions,
+                "inequality": case.inequality().to_string(),
+            })
+        })
+        .collect();
+    json!({
+        "inequality": {
+            "degree": null,
+            "coefficients": null,
+            "denominator": null,
+            "a": null,
+            "b": null,
+            "c": null,
+        },
+        "discriminant": null,
+        "roots": [],
+        "excluded": [],
+        "cases": cases,
+    })
+}
+
+/// one comparison, normalized, with its variable, sign and text.
+fn comparison(inequality: &AbsoluteInequality) -> Value {
+    let mut document = match inequality.rational_inequality() {
+        Some(rational_inequality) => rational(&rational_inequality),
+        None => absolute(inequality),
+    };
+    document["inequality"]["variable"] = inequality.expression().character().into();
+    document["inequality"]["sign"] = sign(inequality.sign()).into();
+    document["inequality"]["text"] = inequality.to_string().into();
+    document
+}
+
+/// the document for a solved inequality, laid out as described above.
+pub fn solved(inequality: &ChainedInequality, solution: &SolutionSet, locale: Locale) -> Value {
+    let mut document = match inequality.inequalities() {
+        [single] => {
+            let mut document = comparison(single);
+            document["chain"] = Value::Null;
+            document
+        }
+        comparisons => {
+            let comparisons: Vec<Value> = comparisons.iter().map(comparison).collect();
+            json!({
+                "inequality": {
+                    "degree": null,
+                    "coefficients": null,
+                    "denominator": null,
+                    "a": null,
+                    "b": null,
+                    "c": null,
+                    "variable": inequality.character(),
+                    "sign": null,
+                    "text": inequality.to_string(),
+                },
+                "discriminant": null,
+                "roots": [],
+                "excluded": [],
+                "cases": null,
+                "chain": comparisons,
+            })
+        }
+    };
+    let intervals: Vec<Value> = solution
+        .intervals()
+        .iter()
+        .map(|interval| {
+            json!({
+                "lower": bound(&interval.lower()),
+                "upper": bound(&interval.upper()),
+            })
+        })
+        .collect();
+    document["ok"] = true.into();
+    document["solution"] = json!({
+        "text": solution.localized(locale).to_string(),
+        "intervals": intervals,
+    });
+    document
+}
+
+/// the document for an input that could not be solved, with the message in `locale`.
+pub fn failed(error: &Error, locale: Locale) -> Value {
+    json!({
+        "ok": false,
+        "error": {
+            "code": error.kind().code(),
+            "message": error.localized(locale).to_string(),
+            "span": {
+                "start": error.span().start,
+                "end": error.span().end,
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Span, parser::parse_chained, solve_set};
+
+    #[test]
+    fn solved_to_json() {
+        let input = "x^2-2<0";
+        let document = solved(
+            &parse_chained(input).unwrap(),
+            &solve_set(input).unwrap(),
+            Locale::English,
+        );
+        assert_eq!(document["inequality"]["c"], "-2");
+        assert_eq!(document["inequality"]["sign"], "<");
+        assert_eq!(document["discriminant"], "8");
+        assert_eq!(document["roots"][0]["exact"], "-√2");
+        assert_eq!(
+            document["solution"]["intervals"],
+            json!([{
+                "lower": {
+                    "exact": "-√2",
+                    "approx": -2f64.sqrt(),
+                    "approximate": false,
+                    "closed": false
+                },
+                "upper": {
+                    "exact": "√2",
+                    "approx": 2f64.sqrt(),
+                    "approximate": false,
+                    "closed": false
+                },
+            }])
+        );
+    }
+
+    #[test]
+    fn first_version_fields_are_kept() {
+        for input in [
+            "x^2-2<0",
+            "x^3-2>=0",
+            "(x-1)/(x+2)>=0",
+            "|x-3|<2",
+            "-3 < x^2-4x < 5",
+        ] {
+            let document = solved(
+                &parse_chained(input).unwrap(),
+                &solve_set(input).unwrap(),
+                Locale::English,
+            );
+            assert_eq!(document["ok"], true);
+            assert!(document["inequality"]["variable"].is_string());
+            assert!(document["inequality"]["text"].is_string());
+            for root in document["roots"].as_array().unwrap() {
+                assert!(root["exact"].is_string());
+                assert!(root["approx"].is_f64());
+            }
+            assert!(document["solution"]["text"].is_string());
+            for interval in document["solution"]["intervals"].as_array().unwrap() {
+                for bound in [&interval["lower"], &interval["upper"]] {
+                    assert!(bound.is_null() || bound["exact"].is_string());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solved_cubic_to_json() {
+        let input = "x^3-2>=0";
+        let document = solved(
+            &parse_chained(input).unwrap(),
+            &solve_set(input).unwrap(),
+            Locale::English,
+        );
+        assert_eq!(document["inequality"]["degree"], 3);
+        assert_eq!(
+            document["inequality"]["coefficients"],
+            json!(["-2", "0", "0", "1"])
+        );
+        assert_eq!(document["inequality"]["a"], Value::Null);
+        assert_eq!(document["discriminant"], Value::Null);
+        assert_eq!(document["roots"][0]["exact"], "≈1.259921");
+        assert_eq!(document["roots"][0]["approximate"], true);
+        assert_eq!(document["roots"][0]["multiplicity"], 1);
+        assert_eq!(
+            document["solution"]["intervals"][0]["lower"]["approx"],
+            2f64.cbrt()
+        );
+    }
+
+    #[test]
+    fn solved_rational_to_json() {
+        let input = "(x-1)/(x+2)>=0";
+        let document = solved(
+            &parse_chained(input).unwrap(),
+            &solve_set(input).unwrap(),
+            Locale::English,
+        );
+        assert_eq!(document["inequality"]["coefficients"], json!(["-1", "1"]));
+        assert_eq!(document["inequality"]["denominator"], json!(["2", "1"]));
+        assert_eq!(document["inequality"]["c"], Value::Null);
+        assert_eq!(document["roots"][0]["exact"], "1");
+        assert_eq!(
+            document["excluded"],
+            json!([{"exact": "-2", "approx": -2.0, "approximate": false}])
+        );
+        assert_eq!(
+            document["solution"]["intervals"][0]["upper"],
+            json!({"exact": "-2", "approx": -2.0, "approximate": false, "closed": false})
+        );
+    }
+
+    #[test]
+    fn solved_absolute_to_json() {
+        let input = "|x-3|<2";
+        let document = solved(
+            &parse_chained(input).unwrap(),
+            &solve_set(input).unwrap(),
+            Locale::English,
+        );
+        assert_eq!(document["inequality"]["text"], "|x - 3| - 2 < 0");
+        assert_eq!(document["inequality"]["coefficients"], Value::Null);
+        assert_eq!(
+            document["cases"],
+            json!([
+                {"conditions": ["x - 3 ≥ 0"], "inequality": "x - 5 < 0"},
+                {"conditions": ["x - 3 < 0"], "inequality": "-x + 1 < 0"},
+            ])
+        );
+        assert_eq!(document["solution"]["text"], "1 < x < 5");
+    }
+
+    #[test]
+    fn solved_chained_to_json() {
+        let input = "-3 < x^2-4x < 5";
+        let document = solved(
+            &parse_chained(input).unwrap(),
+            &solve_set(input).unwrap(),
+            Locale::English,
+        );
+        assert_eq!(document["inequality"]["text"], "-3 < x^2 - 4x < 5");
+        assert_eq!(document["inequality"]["sign"], Value::Null);
+        assert_eq!(
+            document["chain"][0]["inequality"]["text"],
+            "-x^2 + 4x - 3 < 0"
+        );
+        assert_eq!(document["chain"][1]["inequality"]["sign"], "<");
+        assert_eq!(document["chain"][1]["roots"][0]["exact"], "-1");
+        assert_eq!(document["solution"]["text"], "-1 < x < 1 OR 3 < x < 5");
+    }
+
+    #[test]
+    fn failed_to_json() {
+        let error = Error::new(crate::error::ErrorKind::UnexpectedEnd, Span::new(3, 3));
+        assert_eq!(
+            failed(&error, Locale::Korean),
+            json!({
+                "ok": false,
+                "error": {
+                    "code": "unexpected_end",
+                    "message": "입력이 중간에 끝났습니다",
+                    "span": {"start": 3, "end": 3},
+                },
+            })
+        );
+    }
+}
@@ -0,0 +1,366 @@
+use std::cmp::Ordering;
+
+use crate::{
+    absolute::{AbsoluteInequality, Expression},
+    chained::ChainedInequality,
+    polynomial::PolynomialInequality,
+    rational::{RationalFunction, RationalInequality},
+    real::Real,
+    solution::{Bound, SolutionSet},
+    surd::Surd,
+    types::{Number, Polynomial, Quadratic, QuadraticInequality, Sign},
+};
+
+/// something that can be written as a LaTeX math-mode string.
+pub trait ToLatex {
+    fn to_latex(&self) -> String;
+}
+
+/// the absolute value of `number`, as `n` or `\frac{n}{d}`.
+fn magnitude(number: &Number) -> String {
+    let numerator = number.numerator().unsigned_abs();
+    match number.denominator() {
+        1 => numerator.to_string(),
+        d => format!("\\frac{{{}}}{{{}}}", numerator, d),
+    }
+}
+
+/// `\frac{n}{d}` for a fraction, with the sign pulled in front.
+impl ToLatex for Number {
+    fn to_latex(&self) -> String {
+        let sign = if self.signum() == Ordering::Less {
+            "-"
+        } else {
+            ""
+        };
+        format!("{}{}", sign, magnitude(self))
+    }
+}
+
+/// `\frac{-3 - \sqrt{5}}{2}` and the like.
+impl ToLatex for Surd {
+    fn to_latex(&self) -> String {
+        let (p, q, r, s) = self.parts();
+        if q == 0 {
+            let sign = if p < 0 { "-" } else { "" };
+            return match s {
+                1 => p.to_string(),
+                _ => format!("{}\\frac{{{}}}{{{}}}", sign, p.unsigned_abs(), s),
+            };
+        }
+        let radical = match q.unsigned_abs() {
+            1 => format!("\\sqrt{{{}}}", r),
+            k => format!("{}\\sqrt{{{}}}", k, r),
+        };
+        let numerator = match (p, q < 0) {
+            (0, _) => radical,
+            (p, true) => format!("{} - {}", p, radical),
+            (p, false) => format!("{} + {}", p, radical),
+        };
+        match (p, s) {
+            (0, 1) if q < 0 => format!("-{}", numerator),
+            (0, _) if q < 0 => format!("-\\frac{{{}}}{{{}}}", numerator, s),
+            (_, 1) => numerator,
+            _ => format!("\\frac{{{}}}{{{}}}", numerator, s),
+        }
+    }
+}
+
+/// an approximation is written `\approx 1.259921`.
+impl ToLatex for Real {
+    fn to_latex(&self) -> String {
+        match self {
+            Self::Exact(value) => value.to_latex(),
+            Self::Approx(value) => format!("\\approx {}", Real::rounded(*value)),
+        }
+    }
+}
+
+impl ToLatex for Polynomial {
+    fn to_latex(&self) -> String {
+        let mut latex = String::new();
+        for (coefficient, power) in self.terms() {
+            let negative = coefficient.signum() == Ordering::Less;
+            latex.push_str(match (latex.is_empty(), negative) {
+                (true, true) => "-",
+                (true, false) => "",
+                (false, true) => " - ",
+                (false, false) => " + ",
+            });
+            let magnitude = magnitude(&coefficient);
+            if magnitude != "1" || power == 0 {
+                latex.push_str(&magnitude);
+            }
+            match power {
+                0 => {}
+                1 => latex.push_str(self.character()),
+                n => latex.push_str(&format!("{}^{{{}}}", self.character(), n)),
+            }
+        }
+        if latex.is_empty() {
+            latex.push('0');
+        }
+        latex
+    }
+}
+
+impl ToLatex for Quadratic {
+    fn to_latex(&self) -> String {
+        Polynomial::from(self).to_latex()
+    }
+}
+
+/// `\frac{x - 1}{x + 2}`, or just the numerator over a denominator of 1.
+impl ToLatex for RationalFunction {
+    fn to_latex(&self) -> String {
+        if self.is_polynomial() {
+            return self.numerator().to_latex();
+        }
+        format!(
+            "\\frac{{{}}}{{{}}}",
+            self.numerator().to_latex(),
+            self.denominator().to_latex()
+        )
+    }
+}
+
+/// `\left|x - 3\right| - 2`, with fractions as `\frac`.
+impl ToLatex for Expression {
+    fn to_latex(&self) -> String {
+        let operand = |operand: &Self, precedence: u8| {
+            if operand.precedence() < precedence {
+                format!("\\left({}\\right)", operand.to_latex())
+            } else {
+                operand.to_latex()
+            }
+        };
+        match self {
+            Self::Function(function) => function.to_latex(),
+            Self::Abs(argument) => format!("\\left|{}\\right|", argument.to_latex()),
+            Self::Neg(negated) => format!("-{}", operand(negated, 1)),
+            Self::Add(left, right) => match right.split_sign() {
+                (true, right) => format!("{} - {}", left.to_latex(), operand(&right, 1)),
+                (false, right) => format!("{} + {}", left.to_latex(), right.to_latex()),
+            },
+            Self::Mul(left, right) => {
+                let times = match right.as_ref() {
+                    Self::Function(_) if right.precedence() >= 2 => " \\cdot ",
+                    _ => "",
+                };
+                format!("{}{}{}", operand(left, 1), times, operand(right, 2))
+            }
+            Self::Div(left, right) => {
+                format!("\\frac{{{}}}{{{}}}", left.to_latex(), right.to_latex())
+            }
+            Self::Pow(base, exponent) => format!("{}^{{{}}}", operand(base, 3), exponent),
+        }
+    }
+}
+
+impl ToLatex for Sign {
+    fn to_latex(&self) -> String {
+        match self {
+            Self::Lt => "<",
+            Self::Lte => "\\le",
+            Self::Gt => ">",
+            Self::Gte => "\\ge",
+        }
+        .to_string()
+    }
+}
+
+/// the normalized form `ax^{2} + bx + c < 0`.
+impl ToLatex for QuadraticInequality {
+    fn to_latex(&self) -> String {
+        format!(
+            "{} {} 0",
+            self.quadratic().to_latex(),
+            self.sign().to_latex()
+        )
+    }
+}
+
+/// the normalized form `x^{3} - 4x > 0`.
+impl ToLatex for PolynomialInequality {
+    fn to_latex(&self) -> String {
+        format!(
+            "{} {} 0",
+            self.polynomial().to_latex(),
+            self.sign().to_latex()
+        )
+    }
+}
+
+/// the normalized form `\frac{-x + 6}{x - 3} < 0`.
+impl ToLatex for RationalInequality {
+    fn to_latex(&self) -> String {
+        format!(
+            "{} {} 0",
+            self.function().to_latex(),
+            self.sign().to_latex()
+        )
+    }
+}
+
+/// the normalized form `\left|x - 3\right| - 2 < 0`.
+impl ToLatex for AbsoluteInequality {
+    fn to_latex(&self) -> String {
+        format!(
+            "{} {} 0",
+            self.expression().to_latex(),
+            self.sign().to_latex()
+        )
+    }
+}
+
+/// the chain as written, `-3 < x^{2} - 4x < 5`, or the normalized form of a
+/// single comparison.
+impl ToLatex for ChainedInequality {
+    fn to_latex(&self) -> String {
+        if let [inequality] = self.inequalities() {
+            return inequality.to_latex();
+        }
+        let mut latex = self.sides()[0].to_latex();
+        for (sign, side) in self.signs().iter().zip(&self.sides()[1..]) {
+            latex.push_str(&format!(" {} {}", sign.to_latex(), side.to_latex()));
+        }
+        latex
+    }
+}
+
+/// interval notation: `(-\infty, -5) \cup (2, \infty)`, `\emptyset`, `\{3\}`.
+impl ToLatex for SolutionSet {
+    fn to_latex(&self) -> String {
+        if self.is_empty() {
+            return "\\emptyset".to_string();
+        }
+        let intervals: Vec<String> = self
+            .intervals()
+            .iter()
+            .map(|interval| match (interval.lower(), interval.upper()) {
+                (Bound::Closed(l), Bound::Closed(u)) if l == u => {
+                    format!("\\{{{}\\}}", l.to_latex())
+                }
+                (lower, upper) => {
+                    let lower = match lower {
+                        Bound::Unbounded => "(-\\infty".to_string(),
+                        Bound::Open(l) => format!("({}", l.to_latex()),
+                        Bound::Closed(l) => format!("[{}", l.to_latex()),
+                    };
+                    let upper = match upper {
+                        Bound::Unbounded => "\\infty)".to_string(),
+                        Bound::Open(u) => format!("{})", u.to_latex()),
+                        Bound::Closed(u) => format!("{}]", u.to_latex()),
+                    };
+                    format!("{}, {}", lower, upper)
+                }
+            })
+            .collect();
+        intervals.join(" \\cup ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parser::{parse_absolute, parse_chained},
+        solution::Interval,
+    };
+
+    #[test]
+    fn surd_to_latex() {
+        assert_eq!(Surd::integer(-4).to_latex(), "-4");
+        assert_eq!(Surd::rational(-3, 2).to_latex(), "-\\frac{3}{2}");
+        assert_eq!(Surd::new(0, -1, 2, 1).to_latex(), "-\\sqrt{2}");
+        assert_eq!(Surd::new(0, 3, 2, 2).to_latex(), "\\frac{3\\sqrt{2}}{2}");
+        assert_eq!(Surd::new(0, -1, 2, 2).to_latex(), "-\\frac{\\sqrt{2}}{2}");
+        assert_eq!(
+            Surd::new(-3, -1, 5, 2).to_latex(),
+            "\\frac{-3 - \\sqrt{5}}{2}"
+        );
+        assert_eq!(Surd::new(1, 1, 5, 1).to_latex(), "1 + \\sqrt{5}");
+    }
+
+    #[test]
+    fn quadratic_inequality_to_latex() {
+        let quad_ineq =
+            QuadraticInequality::new(Quadratic::new("x".to_string(), -1, 1, 0), Sign::Gte);
+        assert_eq!(quad_ineq.to_latex(), "-x^{2} + x \\ge 0");
+        let quad_ineq =
+            QuadraticInequality::new(Quadratic::new("x".to_string(), 0, 0, 0), Sign::Lte);
+        assert_eq!(quad_ineq.to_latex(), "0 \\le 0");
+    }
+
+    #[test]
+    fn polynomial_inequality_to_latex() {
+        let inequality =
+            PolynomialInequality::new(Polynomial::new("x", vec![0, -4, 0, 1]), Sign::Gt);
+        assert_eq!(inequality.to_latex(), "x^{3} - 4x > 0");
+    }
+
+    #[test]
+    fn rational_inequality_to_latex() {
+        let function = RationalFunction::new(
+            Polynomial::new("x", vec![6, -1]),
+            Polynomial::new("x", vec![-3, 1]),
+        )
+        .unwrap();
+        assert_eq!(
+            RationalInequality::new(function, Sign::Lt).to_latex(),
+            "\\frac{-x + 6}{x - 3} < 0"
+        );
+    }
+
+    #[test]
+    fn absolute_inequality_to_latex() {
+        assert_eq!(
+            parse_absolute("x^2 - 5|x| + 6 < 0").unwrap().to_latex(),
+            "x^{2} - 5\\left|x\\right| + 6 < 0"
+        );
+        assert_eq!(
+            parse_absolute("|x - 1|/|x + 1|^2 >= 1").unwrap().to_latex(),
+            "\\frac{\\left|x - 1\\right|}{\\left|x + 1\\right|^{2}} - 1 \\ge 0"
+        );
+    }
+
+    #[test]
+    fn chained_inequality_to_latex() {
+        assert_eq!(
+            parse_chained("x-1 <= x^2 <= 2x+3").unwrap().to_latex(),
+            "x - 1 \\le x^{2} \\le 2x + 3"
+        );
+        assert_eq!(
+            parse_chained("x^2 > 1").unwrap().to_latex(),
+            "x^{2} - 1 > 0"
+        );
+    }
+
+    #[test]
+    fn solution_set_to_latex() {
+        assert_eq!(SolutionSet::empty("x").to_latex(), "\\emptyset");
+        assert_eq!(SolutionSet::all("x").to_latex(), "(-\\infty, \\infty)");
+        assert_eq!(
+            SolutionSet::from_intervals(
+                "x",
+                vec![
+                    Interval::new(
+                        Bound::Unbounded,
+                        Bound::Closed(Surd::new(-3, -1, 5, 2).into())
+                    ),
+                    Interval::new(
+                        Bound::Closed(Surd::new(-3, 1, 5, 2).into()),
+                        Bound::Unbounded
+                    ),
+                ]
+            )
+            .to_latex(),
+            "(-\\infty, \\frac{-3 - \\sqrt{5}}{2}] \\cup [\\frac{-3 + \\sqrt{5}}{2}, \\infty)"
+        );
+        assert_eq!(
+            SolutionSet::from_intervals("x", vec![Interval::point(Surd::integer(3).into())])
+                .to_latex(),
+            "\\{3\\}"
+        );
+    }
+}
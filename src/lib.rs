@@ -1,30 +1,116 @@
+//! solve quadratic inequalities exactly, from a string such as `x^2+3x-10>0` or
+//! from coefficients. polynomial inequalities of higher degree, and inequalities
+//! between fractions of polynomials, are solved with a sign chart, exactly wherever
+//! the roots can be written with at most one square root:
+//!
+//! ```
+//! use quadratic_inequality::{solve_set, Number, Quadratic, QuadraticInequality, Sign};
+//!
+//! // x^2 + 3x - 10 > 0
+//! let inequality = QuadraticInequality::new(Quadratic::new("x", 1, 3, -10), Sign::Gt);
+//! let solution = inequality.solve().unwrap();
+//! assert_eq!(solution.to_string(), "x < -5 OR x > 2");
+//! assert_eq!(solve_set("x^2+3x-10>0").unwrap(), solution);
+//!
+//! // 1/2 x^2 - x < 0
+//! let half = Number::from_fraction(1, 2).unwrap();
+//! let inequality = QuadraticInequality::new(Quadratic::new("x", half, -1, 0), Sign::Lt);
+//! assert_eq!(inequality.solve().unwrap().to_string(), "0 < x < 2");
+//!
+//! // (x - 1)^2 (x + 2) (x - 3) <= 0
+//! assert_eq!(solve_set("(x-1)^2(x+2)(x-3)<=0").unwrap().to_string(), "-2 ≤ x ≤ 3");
+//!
+//! // the denominator is never 0, even where the inequality allows equality
+//! assert_eq!(solve_set("(x-1)/(x+2)>=0").unwrap().to_string(), "x < -2 OR x ≥ 1");
+//!
+//! // every comparison of a chain must hold
+//! assert_eq!(solve_set("x-1 <= x^2 <= 2x+3").unwrap().to_string(), "-1 ≤ x ≤ 3");
+//! ```
+
+pub mod absolute;
+pub mod chained;
 pub mod error;
+pub mod explanation;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod json;
+pub mod latex;
+pub mod locale;
+pub mod mathml;
 pub mod parser;
+pub mod polynomial;
+pub mod rational;
+pub mod real;
+pub mod solution;
+pub mod surd;
 pub mod types;
+pub mod web;
+mod wide;
+
+use error::{Error, Result, Span};
+use explanation::Explanation;
+use locale::{Locale, Localize};
+use parser::parse_chained;
+use solution::SolutionSet;
 
-use error::Result;
-use parser::parse;
+pub use absolute::{AbsoluteInequality, Expression};
+pub use chained::ChainedInequality;
+pub use polynomial::{PolynomialInequality, Root};
+pub use rational::{RationalFunction, RationalInequality};
+pub use types::{Number, Polynomial, Quadratic, QuadraticInequality, Sign};
 
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
-#[cfg(not(target_arch = "wasm32"))]
+/// read the inequality and solve it, returning both.
+pub fn parse_and_solve(input: &str) -> Result<(ChainedInequality, SolutionSet)> {
+    let inequality = parse_chained(input)?;
+    let solution = inequality
+        .solve()
+        .map_err(|kind| Error::new(kind, Span::new(0, input.len())))?;
+    Ok((inequality, solution))
+}
+
+/// solve the inequality and return its solution set.
+pub fn solve_set(input: &str) -> Result<SolutionSet> {
+    Ok(parse_and_solve(input)?.1)
+}
+
+/// solve the inequality, recording each step of the way.
+pub fn explain(input: &str) -> Result<Explanation> {
+    parse_chained(input)?
+        .explain()
+        .map_err(|kind| Error::new(kind, Span::new(0, input.len())))
+}
+
+/// solve the inequality and write the answer out in English.
 pub fn solve(input: &str) -> Result<String> {
-    Ok(parse(input)?.get_solution())
+    solve_in(input, Locale::English)
 }
 
-#[cfg(target_arch = "wasm32")]
-#[wasm_bindgen]
-pub fn solve(input: &str) -> String {
-    match parse(input) {
-        Ok(result) => result.get_solution(),
-        Err(_) => "Something went wrong!".to_string(),
+/// like `solve`, with the answer written in `locale`.
+pub fn solve_in(input: &str, locale: Locale) -> Result<String> {
+    Ok(solve_set(input)?.localized(locale).to_string())
+}
+
+/// the normalized inequality and its solution as a MathML `<math>` element, or
+/// what went wrong as a `<p class="error">`, in the language tagged `lang`.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn solve_mathml(input: &str, lang: &str) -> String {
+    let locale: Locale = lang.parse().unwrap_or_default();
+    match parse_and_solve(input) {
+        Ok((inequality, solution)) => mathml::problem_and_answer(&inequality, &solution),
+        Err(e) => format!(
+            "<p class=\"error\">{}</p>",
+            mathml::escape(&e.localized(locale).to_string())
+        ),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use solution::{Bound, Interval};
+    use surd::Surd;
 
     #[test]
     fn solve_quadratic_inequality() {
@@ -33,4 +119,424 @@ mod tests {
         assert_eq!(solve("7x+10+x^2>=0"), Ok("x ≤ -5 OR x ≥ -2".to_string()));
         assert_eq!(solve("x^2+6x+4-x<=0"), Ok("-4 ≤ x ≤ -1".to_string()));
     }
+
+    #[test]
+    fn solve_quadratic_inequality_to_set() {
+        assert_eq!(
+            solve_set("x^2+3x-10>0").unwrap().intervals(),
+            &[
+                Interval::new(Bound::Unbounded, Bound::Open(Surd::integer(-5).into())),
+                Interval::new(Bound::Open(Surd::integer(2).into()), Bound::Unbounded),
+            ]
+        );
+        assert!(solve_set("x^2+1<0").unwrap().is_empty());
+        let (inequality, solution) = parse_and_solve("x^2+3x>10").unwrap();
+        assert_eq!(inequality.to_string(), "x^2 + 3x - 10 > 0");
+        assert_eq!(solution, solve_set("x^2+3x-10>0").unwrap());
+    }
+
+    #[test]
+    fn solve_with_exact_roots() {
+        assert_eq!(solve("x^2-2<0"), Ok("-√2 < x < √2".to_string()));
+        assert_eq!(
+            solve("x^2+3x+1>=0"),
+            Ok("x ≤ (-3 - √5)/2 OR x ≥ (-3 + √5)/2".to_string())
+        );
+        assert_eq!(solve("4x^2-1>0"), Ok("x < -1/2 OR x > 1/2".to_string()));
+    }
+
+    #[test]
+    fn solve_with_fractional_coefficients() {
+        assert_eq!(solve("0.5x^2-x<1"), Ok("1 - √3 < x < 1 + √3".to_string()));
+        assert_eq!(solve("1/2x^2+3/4x>0"), Ok("x < -3/2 OR x > 0".to_string()));
+        assert_eq!(
+            solve("x^2>1/0").map_err(|e| e.kind().clone()),
+            Err(error::ErrorKind::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn solve_large_coefficients() {
+        assert_eq!(
+            solve("50000x^2+99999x-1>0"),
+            Ok(
+                "x < (-99999 - √10000000001)/100000 OR x > (-99999 + √10000000001)/100000"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            solve("3037000500x^2+3037000500x+1>0"),
+            Err(Error::new(error::ErrorKind::Overflow, Span::new(0, 29)))
+        );
+        assert_eq!(
+            solve("x^2>99999999999999999999"),
+            Err(Error::new(error::ErrorKind::Overflow, Span::new(4, 24)))
+        );
+    }
+
+    #[test]
+    fn solve_degenerate_inequality() {
+        assert_eq!(solve("x^2+3x>x^2-6"), Ok("x > -2".to_string()));
+        assert_eq!(solve("x^2+1>x^2"), Ok("all real number".to_string()));
+        assert_eq!(solve("x^2<=x^2-1"), Ok("no solution".to_string()));
+    }
+
+    #[test]
+    fn solve_in_korean() {
+        assert_eq!(
+            solve_in("x^2+3x-10>0", Locale::Korean),
+            Ok("x < -5 또는 x > 2".to_string())
+        );
+        assert_eq!(
+            solve_in("x^2+1<0", Locale::Korean),
+            Ok("해가 없다".to_string())
+        );
+        assert_eq!(
+            solve_in("x^2>1/0", Locale::Korean)
+                .unwrap_err()
+                .localized(Locale::Korean)
+                .to_string(),
+            "0으로 나눌 수 없습니다"
+        );
+        assert_eq!(
+            explain("x^2-1<0")
+                .unwrap()
+                .localized(Locale::Korean)
+                .to_string(),
+            "1. 동류항을 정리합니다: x^2 - 1 < 0\n\
+             2. 판별식: D = b^2 - 4ac = 0^2 - 4·1·(-1) = 4\n\
+             3. D > 0이므로 서로 다른 두 근 x = -1, x = 1을(를) 가집니다\n\
+             4. 이차식은 두 근의 사이에서 < 0입니다\n\
+             5. 답: -1 < x < 1"
+        );
+    }
+
+    #[test]
+    fn solve_to_mathml() {
+        assert_eq!(
+            solve_mathml("x^2<1", "en"),
+            "<math display=\"block\"><mrow><mrow><msup><mi>x</mi><mn>2</mn></msup>\
+             <mo>−</mo><mn>1</mn></mrow><mo>&lt;</mo><mn>0</mn></mrow><mo>⇔</mo><mi>x</mi>\
+             <mo>∈</mo><mrow><mo>(</mo><mrow><mo>−</mo><mn>1</mn></mrow><mo>,</mo><mn>1</mn>\
+             <mo>)</mo></mrow></math>"
+        );
+        assert_eq!(
+            solve_mathml("x^2<>1", "ko"),
+            "<p class=\"error\">&lt;&gt;은(는) 부등호가 아닙니다</p>"
+        );
+    }
+
+    #[test]
+    fn explain_quadratic_inequality() {
+        assert_eq!(
+            explain("-x^2+2x>x-6").unwrap().to_string(),
+            "1. move every term to the left-hand side: -x^2 + 2x - (x - 6) > 0\n\
+             2. collect like terms: -x^2 + x + 6 > 0\n\
+             3. multiply both sides by -1, which flips the sign: x^2 - x - 6 < 0\n\
+             4. discriminant: D = b^2 - 4ac = (-1)^2 - 4·1·(-6) = 25\n\
+             5. D > 0, so there are two roots x = -2 and x = 3\n\
+             6. the quadratic is < 0 between the roots\n\
+             7. answer: -2 < x < 3"
+        );
+        assert_eq!(
+            explain("x^2+4x+4>0").unwrap().to_string(),
+            "1. collect like terms: x^2 + 4x + 4 > 0\n\
+             2. discriminant: D = b^2 - 4ac = 4^2 - 4·1·4 = 0\n\
+             3. D = 0, so there is a double root x = -2\n\
+             4. the quadratic is 0 at -2 and positive elsewhere, so find where it is > 0\n\
+             5. answer: all real number with x ≠ -2"
+        );
+    }
+
+    #[test]
+    fn solve_polynomial_inequality() {
+        assert_eq!(solve("x^3-4x>0"), Ok("-2 < x < 0 OR x > 2".to_string()));
+        assert_eq!(solve("(x-1)^2(x+2)(x-3)<=0"), Ok("-2 ≤ x ≤ 3".to_string()));
+        assert_eq!(
+            solve("(x-1)^2(x+2)(x-3)<0"),
+            Ok("-2 < x < 1 OR 1 < x < 3".to_string())
+        );
+        assert_eq!(
+            solve("x^4 < 5x^2 - 6"),
+            Ok("-√3 < x < -√2 OR √2 < x < √3".to_string())
+        );
+        assert_eq!(solve("x^3 > 2"), Ok("x > ≈1.259921".to_string()));
+        assert_eq!(solve("x^4+1<0"), Ok("no solution".to_string()));
+    }
+
+    #[test]
+    fn solve_with_exact_and_approximate_roots() {
+        // x^2 - 2 divides, but its roots are not the ones of x^5 - x - 1
+        assert_eq!(
+            solve("(x^5-x-1)(x^2-2)>0"),
+            Ok("-√2 < x < ≈1.167304 OR x > √2".to_string())
+        );
+        assert_eq!(
+            solve("(x^2-2)(x^2-3)(x^3-2)>0"),
+            Ok("-√3 < x < -√2 OR ≈1.259921 < x < √2 OR x > √3".to_string())
+        );
+        // rational coefficients grow quickly in Euclid's algorithm
+        assert_eq!(
+            solve("(x-1/3)(x-2/7)(x^3-2)>0"),
+            Ok("2/7 < x < 1/3 OR x > ≈1.259921".to_string())
+        );
+        assert_eq!(
+            solve("(x^2-3)(x^2-2)(x^5-x-1)<0"),
+            Ok("x < -√3 OR -√2 < x < ≈1.167304 OR √2 < x < √3".to_string())
+        );
+        // and so do integer ones in the remainders against the derivative
+        assert_eq!(
+            solve("x^12-3x^5+2x-7<0"),
+            Ok("≈-1.120897 < x < ≈1.241054".to_string())
+        );
+        assert_eq!(solve("x^11-2x^4+2x-7<0"), Ok("x < ≈1.221159".to_string()));
+        assert_eq!(
+            solve("x^12-3x^3+2x-3<0"),
+            Ok("≈-1.043919 < x < ≈1.148071".to_string())
+        );
+    }
+
+    #[test]
+    fn explain_polynomial_inequality() {
+        assert_eq!(
+            explain("(x-1)^2(x+2)(x-3)<=0").unwrap().to_string(),
+            "1. collect like terms: x^4 - 3x^3 - 3x^2 + 11x - 6 ≤ 0\n\
+             2. find the real roots: x = -2, x = 1 (double root), x = 3\n\
+             3. sign chart: + | -2 | - | 1 | - | 3 | +\n\
+             4. the left-hand side is ≤ 0 on the intervals marked -, roots included\n\
+             5. answer: -2 ≤ x ≤ 3"
+        );
+        assert_eq!(
+            explain("x^3>4x")
+                .unwrap()
+                .localized(Locale::Korean)
+                .to_string(),
+            "1. 모든 항을 좌변으로 이항합니다: x^3 - (4x) > 0\n\
+             2. 동류항을 정리합니다: x^3 - 4x > 0\n\
+             3. 실근을 구합니다: x = -2, x = 0, x = 2\n\
+             4. 부호표: - | -2 | + | 0 | - | 2 | +\n\
+             5. 좌변은 부호가 +인 구간에서 > 0입니다\n\
+             6. 답: -2 < x < 0 또는 x > 2"
+        );
+    }
+
+    #[test]
+    fn solve_rational_inequality() {
+        assert_eq!(solve("(x-1)/(x+2)>=0"), Ok("x < -2 OR x ≥ 1".to_string()));
+        assert_eq!(solve("x/(x-3)<2"), Ok("x < 3 OR x > 6".to_string()));
+        assert_eq!(
+            solve("(x+1)/(x^2-2)>=0"),
+            Ok("-√2 < x ≤ -1 OR x > √2".to_string())
+        );
+        assert_eq!(
+            solve("(x-1)/(x-1)>=0"),
+            Ok("all real number with x ≠ 1".to_string())
+        );
+        assert_eq!(solve("1/(x^2+1)<=0"), Ok("no solution".to_string()));
+        assert_eq!(
+            solve("x/(x-x)>0"),
+            Err(Error::new(
+                error::ErrorKind::DivisionByZero,
+                Span::new(1, 7)
+            ))
+        );
+    }
+
+    #[test]
+    fn explain_rational_inequality() {
+        assert_eq!(
+            explain("x/(x-3)<2").unwrap().to_string(),
+            "1. move every term to the left-hand side: x/(x - 3) - (2) < 0\n\
+             2. write as a single fraction: (-x + 6)/(x - 3) < 0\n\
+             3. the denominator is 0 at x = 3, which is left out of the answer\n\
+             4. the numerator is 0 at x = 6\n\
+             5. sign chart: - | 3 | + | 6 | -\n\
+             6. the left-hand side is < 0 on the intervals marked -\n\
+             7. answer: x < 3 OR x > 6"
+        );
+        assert_eq!(
+            explain("(x-1)/(x+2)>=0")
+                .unwrap()
+                .localized(Locale::Korean)
+                .to_string(),
+            "1. 통분하여 하나의 분수로 나타냅니다: (x - 1)/(x + 2) ≥ 0\n\
+             2. 분모가 0이 되는 x = -2은(는) 답에서 제외합니다\n\
+             3. 분자가 0이 되는 곳: x = 1\n\
+             4. 부호표: + | -2 | - | 1 | +\n\
+             5. 좌변은 부호가 +인 구간에서 ≥ 0입니다 (근 포함)\n\
+             6. 답: x < -2 또는 x ≥ 1"
+        );
+    }
+
+    #[test]
+    fn solve_absolute_value_inequality() {
+        assert_eq!(solve("|x-3|<2"), Ok("1 < x < 5".to_string()));
+        assert_eq!(solve("|x^2-4|>=3x"), Ok("x ≤ 1 OR x ≥ 4".to_string()));
+        assert_eq!(
+            solve("x^2-5|x|+6<0"),
+            Ok("-3 < x < -2 OR 2 < x < 3".to_string())
+        );
+    }
+
+    #[test]
+    fn explain_absolute_value_inequality() {
+        assert_eq!(
+            explain("|x-3|<2").unwrap().to_string(),
+            "1. split into cases on the sign of x - 3\n\
+             2. when x - 3 ≥ 0 the inequality is x - 5 < 0, so 3 ≤ x < 5\n\
+             3. when x - 3 < 0 the inequality is -x + 1 < 0, so 1 < x < 3\n\
+             4. answer: 1 < x < 5"
+        );
+        assert_eq!(
+            explain("x^2-5|x|+6<0")
+                .unwrap()
+                .localized(Locale::Korean)
+                .to_string(),
+            "1. x의 부호에 따라 경우를 나눕니다\n\
+             2. x ≥ 0일 때 부등식은 x^2 - 5x + 6 < 0이므로 2 < x < 3\n\
+             3. x < 0일 때 부등식은 x^2 + 5x + 6 < 0이므로 -3 < x < -2\n\
+             4. 답: -3 < x < -2 또는 2 < x < 3"
+        );
+    }
+
+    #[test]
+    fn solve_chained_inequality() {
+        assert_eq!(
+            solve("-3 < x^2-4x < 5"),
+            Ok("-1 < x < 1 OR 3 < x < 5".to_string())
+        );
+        assert_eq!(solve("x-1 <= x^2 <= 2x+3"), Ok("-1 ≤ x ≤ 3".to_string()));
+    }
+
+    #[test]
+    fn explain_chained_inequality() {
+        let explanation = explain("x-1 <= x^2 <= 2x+3").unwrap();
+        assert_eq!(
+            explanation.steps()[0].to_string(),
+            "solve x - 1 ≤ x^2 and x^2 ≤ 2x + 3 one at a time; \
+             the answer is where all of them hold"
+        );
+        let comparisons: Vec<_> = explanation
+            .steps()
+            .iter()
+            .filter(|step| matches!(step, explanation::Step::Comparison { .. }))
+            .collect();
+        assert_eq!(
+            comparisons
+                .iter()
+                .map(|step| step.localized(Locale::Korean).to_string())
+                .collect::<Vec<_>>(),
+            [
+                "x - 1 ≤ x^2의 해: 모든 실수",
+                "x^2 ≤ 2x + 3의 해: -1 ≤ x ≤ 3"
+            ]
+        );
+        assert_eq!(
+            comparisons[1].to_string(),
+            "solution of x^2 ≤ 2x + 3: -1 ≤ x ≤ 3"
+        );
+        assert_eq!(
+            explanation.steps().last(),
+            Some(&explanation::Step::Answer(
+                solve_set("-1 <= x <= 3").unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn explain_degenerate_inequality() {
+        assert_eq!(
+            explain("x^2<=x^2-1").unwrap().to_string(),
+            "1. move every term to the left-hand side: x^2 - (x^2 - 1) ≤ 0\n\
+             2. collect like terms: 1 ≤ 0\n\
+             3. 1 ≤ 0 is never true\n\
+             4. answer: no solution"
+        );
+        assert_eq!(
+            explain("1/2 - x >= 0").unwrap().steps()[1..],
+            [
+                explanation::Step::FlipSign {
+                    quadratic: Quadratic::from_polynomial(
+                        parse_chained("x - 1/2 > 0").unwrap().sides()[0]
+                            .clone()
+                            .into_function()
+                            .and_then(RationalFunction::into_polynomial)
+                            .unwrap()
+                    )
+                    .unwrap(),
+                    sign: types::Sign::Lte,
+                },
+                explanation::Step::Answer(solve_set("x <= 1/2").unwrap()),
+            ]
+        );
+        assert!(explain("x^2>y").is_err());
+    }
+
+    #[test]
+    fn solve_never_panics_on_short_input() {
+        let alphabet = [
+            "x", "y", "2", "0", ".", "/", "^", "+", "-", "*", "(", ")", "<", "=", "≥", " ",
+        ];
+        let mut inputs = vec![String::new()];
+        for _ in 0..4 {
+            inputs = inputs
+                .iter()
+                .flat_map(|input| alphabet.iter().map(move |c| format!("{}{}", input, c)))
+                .collect();
+            for input in &inputs {
+                let _ = solve(input);
+            }
+        }
+    }
+
+    #[test]
+    fn solve_never_panics_on_extreme_input() {
+        let max = i64::MAX.to_string();
+        let inputs = [
+            format!("x^2>-{}-1", max),
+            format!("x^2+{}x>-{}-1", max, max),
+            format!("-{}x-1>0", max),
+            format!("({}x+{})^2<0", max, max),
+            format!("{}/{}x^2-x>{}", max, max, max),
+            format!("x^{}>0", max),
+            format!("x^{}0>0", max),
+            "x^4294967295>0".to_string(),
+            "0.0000000000000000000000000000000000000001x>1".to_string(),
+            "1".repeat(100) + "x>0",
+            "(".repeat(10000) + "x",
+            "|".repeat(10000) + "x",
+            "x>0".to_string() + &")".repeat(10000),
+            "\u{1F600}x^2\u{0}>\u{a0}0\n".to_string(),
+        ];
+        for input in &inputs {
+            let _ = solve(input);
+            if let Err(e) = solve(input) {
+                let _ = e.render(input);
+            }
+        }
+    }
+
+    #[test]
+    // `is_multiple_of` is too new for the compilers this crate still builds with
+    #[allow(unknown_lints, clippy::manual_is_multiple_of)]
+    fn solve_never_panics_on_random_input() {
+        let alphabet: Vec<char> = "x2509./^+-*()<>=≤| \t√".chars().collect();
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..20000 {
+            let mut input = String::new();
+            loop {
+                // xorshift, deterministic so a failure can be reproduced
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                if seed % 24 == 0 {
+                    break;
+                }
+                input.push(alphabet[(seed >> 32) as usize % alphabet.len()]);
+            }
+            if let Err(e) = solve(&input) {
+                let _ = e.render(&input);
+            }
+        }
+    }
 }
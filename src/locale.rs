@@ -0,0 +1,531 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    error::{ErrorKind, Expected},
+    explanation::{Region, Step},
+    polynomial::Root,
+    types::Sign,
+};
+
+/// the language messages are written in.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Locale {
+    #[default]
+    English,
+    Korean,
+}
+impl Locale {
+    /// the language tag, `en` or `ko`.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Korean => "ko",
+        }
+    }
+}
+
+/// accepts a language tag such as `ko`, `ko-KR` or `ko_KR.UTF-8`.
+impl FromStr for Locale {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.split(['-', '_', '.']).next();
+        match language.map(str::to_ascii_lowercase).as_deref() {
+            Some("en") => Ok(Self::English),
+            Some("ko") => Ok(Self::Korean),
+            _ => Err(format!("unknown language: {}", s)),
+        }
+    }
+}
+
+/// something that can be written out in more than one language. its `Display`
+/// writes it in English.
+pub trait Localize {
+    fn fmt_localized(&self, f: &mut fmt::Formatter<'_>, locale: Locale) -> fmt::Result;
+    fn localized(&self, locale: Locale) -> Localized<'_, Self> {
+        Localized {
+            value: self,
+            locale,
+        }
+    }
+}
+
+/// a value displayed in a given language; see [`Localize::localized`].
+pub struct Localized<'a, T: ?Sized> {
+    value: &'a T,
+    locale: Locale,
+}
+impl<T: Localize + ?Sized> fmt::Display for Localized<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt_localized(f, self.locale)
+    }
+}
+
+// the catalog: every phrase the crate shows, in every language.
+impl Locale {
+    pub(crate) fn no_solution(&self) -> &'static str {
+        match self {
+            Self::English => "no solution",
+            Self::Korean => "해가 없다",
+        }
+    }
+    pub(crate) fn all_real(&self) -> &'static str {
+        match self {
+            Self::English => "all real number",
+            Self::Korean => "모든 실수",
+        }
+    }
+    pub(crate) fn or(&self) -> &'static str {
+        match self {
+            Self::English => "OR",
+            Self::Korean => "또는",
+        }
+    }
+    pub(crate) fn fmt_all_real_except(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        character: &str,
+        point: &dyn fmt::Display,
+    ) -> fmt::Result {
+        match self {
+            Self::English => write!(f, "all real number with {} ≠ {}", character, point),
+            Self::Korean => write!(f, "{} ≠ {}인 모든 실수", character, point),
+        }
+    }
+
+    fn expected(&self, expected: &Expected) -> &'static str {
+        match (self, expected) {
+            (Self::English, Expected::Operand) => "a number or variable",
+            (Self::English, Expected::InequalitySign) => "an inequality sign",
+            (Self::English, Expected::Exponent) => "an exponent",
+            (Self::English, Expected::ClosingParenthesis) => "')'",
+            (Self::English, Expected::ClosingBar) => "'|'",
+            // with the subject particle, which depends on the last syllable
+            (Self::Korean, Expected::Operand) => "수나 변수가",
+            (Self::Korean, Expected::InequalitySign) => "부등호가",
+            (Self::Korean, Expected::Exponent) => "지수가",
+            (Self::Korean, Expected::ClosingParenthesis) => "닫는 괄호 ')'가",
+            (Self::Korean, Expected::ClosingBar) => "절댓값을 닫는 '|'가",
+        }
+    }
+    pub(crate) fn fmt_error(&self, f: &mut fmt::Formatter<'_>, kind: &ErrorKind) -> fmt::Result {
+        match self {
+            Self::English => match kind {
+                ErrorKind::InvalidIneqSign(sign) => write!(f, "{} is not an inequality sign", sign),
+                ErrorKind::InvalidQuadratic => write!(f, "invalid quadratic"),
+                ErrorKind::InvalidPolynomial => write!(f, "not a polynomial"),
+                ErrorKind::AbsoluteValue => write!(f, "an absolute value is not allowed here"),
+                ErrorKind::InvalidCharacter { expected, found } => write!(
+                    f,
+                    "invalid character: expected {}, found {}",
+                    expected, found
+                ),
+                ErrorKind::DivisionByZero => write!(f, "division by zero"),
+                ErrorKind::NegativeSquareRoot => write!(f, "square root of a negative number"),
+                ErrorKind::Overflow => write!(f, "number too large"),
+                ErrorKind::DegreeTooHigh => write!(f, "degree too high"),
+                ErrorKind::TooDeeplyNested => write!(f, "too many nested parentheses"),
+                ErrorKind::TooManyAbsoluteValues => write!(f, "too many absolute values"),
+                ErrorKind::UnexpectedEnd => write!(f, "unexpected end of input"),
+                ErrorKind::TrailingInput { found } => write!(f, "unexpected input '{}'", found),
+                ErrorKind::Syntax { expected, after } => match (expected, after) {
+                    (Some(expected), Some(after)) => {
+                        write!(f, "expected {} after '{}'", self.expected(expected), after)
+                    }
+                    (Some(expected), None) => write!(f, "expected {}", self.expected(expected)),
+                    (None, _) => write!(f, "unexpected input"),
+                },
+            },
+            Self::Korean => match kind {
+                ErrorKind::InvalidIneqSign(sign) => write!(f, "{}은(는) 부등호가 아닙니다", sign),
+                ErrorKind::InvalidQuadratic => write!(f, "올바른 이차식이 아닙니다"),
+                ErrorKind::InvalidPolynomial => write!(f, "다항식이 아닙니다"),
+                ErrorKind::AbsoluteValue => write!(f, "여기에는 절댓값을 쓸 수 없습니다"),
+                ErrorKind::InvalidCharacter { expected, found } => write!(
+                    f,
+                    "잘못된 문자: {}이(가) 와야 하는데 {}이(가) 있습니다",
+                    expected, found
+                ),
+                ErrorKind::DivisionByZero => write!(f, "0으로 나눌 수 없습니다"),
+                ErrorKind::NegativeSquareRoot => write!(f, "음수의 제곱근은 실수가 아닙니다"),
+                ErrorKind::Overflow => write!(f, "수가 너무 큽니다"),
+                ErrorKind::DegreeTooHigh => write!(f, "차수가 너무 높습니다"),
+                ErrorKind::TooDeeplyNested => write!(f, "괄호가 너무 깊게 중첩되어 있습니다"),
+                ErrorKind::TooManyAbsoluteValues => write!(f, "절댓값이 너무 많습니다"),
+                ErrorKind::UnexpectedEnd => write!(f, "입력이 중간에 끝났습니다"),
+                ErrorKind::TrailingInput { found } => write!(f, "예상하지 못한 입력 '{}'", found),
+                ErrorKind::Syntax { expected, after } => match (expected, after) {
+                    (Some(expected), Some(after)) => write!(
+                        f,
+                        "'{}' 뒤에 {} 와야 합니다",
+                        after,
+                        self.expected(expected)
+                    ),
+                    (Some(expected), None) => {
+                        write!(f, "{} 와야 합니다", self.expected(expected))
+                    }
+                    (None, _) => write!(f, "예상하지 못한 입력입니다"),
+                },
+            },
+        }
+    }
+
+    /// `x = 0, x = 1 (double root)`.
+    fn fmt_roots(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        character: &str,
+        roots: &[Root],
+    ) -> fmt::Result {
+        for (i, root) in roots.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} = {}", character, root.value())?;
+            match (self, root.multiplicity()) {
+                (_, 1) => {}
+                (Self::English, 2) => write!(f, " (double root)")?,
+                (Self::English, 3) => write!(f, " (triple root)")?,
+                (Self::English, m) => write!(f, " (multiplicity {})", m)?,
+                (Self::Korean, m) => write!(f, " ({}중근)", m)?,
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn fmt_step(&self, f: &mut fmt::Formatter<'_>, step: &Step) -> fmt::Result {
+        match step {
+            Step::MoveToOneSide { left, sign, right } => match self {
+                Self::English => write!(
+                    f,
+                    "move every term to the left-hand side: {} - ({}) {} 0",
+                    left, right, sign
+                ),
+                Self::Korean => write!(
+                    f,
+                    "모든 항을 좌변으로 이항합니다: {} - ({}) {} 0",
+                    left, right, sign
+                ),
+            },
+            Step::CollectTerms { polynomial, sign } => match self {
+                Self::English => write!(f, "collect like terms: {} {} 0", polynomial, sign),
+                Self::Korean => write!(f, "동류항을 정리합니다: {} {} 0", polynomial, sign),
+            },
+            Step::FlipSign { quadratic, sign } => match self {
+                Self::English => write!(
+                    f,
+                    "multiply both sides by -1, which flips the sign: {} {} 0",
+                    quadratic, sign
+                ),
+                Self::Korean => write!(
+                    f,
+                    "양변에 -1을 곱하고 부등호의 방향을 바꿉니다: {} {} 0",
+                    quadratic, sign
+                ),
+            },
+            Step::Constant { c, sign, holds } => match (self, holds) {
+                (Self::English, true) => write!(f, "{} {} 0 is always true", c, sign),
+                (Self::English, false) => write!(f, "{} {} 0 is never true", c, sign),
+                (Self::Korean, true) => write!(f, "{} {} 0은 항상 참입니다", c, sign),
+                (Self::Korean, false) => write!(f, "{} {} 0은 항상 거짓입니다", c, sign),
+            },
+            Step::Discriminant { .. } => {
+                let label = match self {
+                    Self::English => "discriminant",
+                    Self::Korean => "판별식",
+                };
+                write!(f, "{}: ", label)?;
+                step.fmt_discriminant(f)
+            }
+            Step::Roots { character, roots } => match (self, roots.as_slice()) {
+                (Self::English, []) => write!(f, "D < 0, so there is no real root"),
+                (Self::English, [root]) => {
+                    write!(
+                        f,
+                        "D = 0, so there is a double root {} = {}",
+                        character, root
+                    )
+                }
+                (Self::English, [first, second]) => write!(
+                    f,
+                    "D > 0, so there are two roots {} = {} and {} = {}",
+                    character, first, character, second
+                ),
+                (Self::Korean, []) => write!(f, "D < 0이므로 실근이 없습니다"),
+                (Self::Korean, [root]) => {
+                    write!(
+                        f,
+                        "D = 0이므로 중근 {} = {}을(를) 가집니다",
+                        character, root
+                    )
+                }
+                (Self::Korean, [first, second]) => write!(
+                    f,
+                    "D > 0이므로 서로 다른 두 근 {} = {}, {} = {}을(를) 가집니다",
+                    character, first, character, second
+                ),
+                (_, roots) => {
+                    write!(
+                        f,
+                        "{} ",
+                        match self {
+                            Self::English => "the roots are",
+                            Self::Korean => "근:",
+                        }
+                    )?;
+                    for (i, root) in roots.iter().enumerate() {
+                        if i > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{} = {}", character, root)?;
+                    }
+                    Ok(())
+                }
+            },
+            Step::ChooseIntervals {
+                sign,
+                region,
+                inclusive,
+            } => match self {
+                Self::English => write!(
+                    f,
+                    "the quadratic is {} 0 {} the roots{}",
+                    sign,
+                    match region {
+                        Region::Between => "between",
+                        Region::Outside => "outside",
+                    },
+                    if *inclusive { ", roots included" } else { "" }
+                ),
+                Self::Korean => write!(
+                    f,
+                    "이차식은 두 근의 {}에서 {} 0입니다{}",
+                    match region {
+                        Region::Between => "사이",
+                        Region::Outside => "바깥",
+                    },
+                    sign,
+                    if *inclusive { " (근 포함)" } else { "" }
+                ),
+            },
+            Step::NeverNegative { sign, double_root } => match (self, double_root) {
+                (Self::English, Some(root)) => write!(
+                    f,
+                    "the quadratic is 0 at {} and positive elsewhere, so find where it is {} 0",
+                    root, sign
+                ),
+                (Self::English, None) => write!(
+                    f,
+                    "the quadratic is always positive, so find where it is {} 0",
+                    sign
+                ),
+                (Self::Korean, Some(root)) => write!(
+                    f,
+                    "이차식은 {}에서 0이고 나머지에서는 양수이므로, {} 0인 범위를 찾습니다",
+                    root, sign
+                ),
+                (Self::Korean, None) => {
+                    write!(f, "이차식은 항상 양수이므로, {} 0인 범위를 찾습니다", sign)
+                }
+            },
+            Step::PolynomialRoots { character, roots } => {
+                if roots.is_empty() {
+                    return match self {
+                        Self::English => write!(f, "there is no real root"),
+                        Self::Korean => write!(f, "실근이 없습니다"),
+                    };
+                }
+                match self {
+                    Self::English => write!(f, "find the real roots: ")?,
+                    Self::Korean => write!(f, "실근을 구합니다: ")?,
+                }
+                self.fmt_roots(f, character, roots)
+            }
+            Step::SignChart { .. } => {
+                let label = match self {
+                    Self::English => "sign chart",
+                    Self::Korean => "부호표",
+                };
+                write!(f, "{}: ", label)?;
+                step.fmt_sign_chart(f)
+            }
+            Step::ChooseSigns { sign, inclusive } => {
+                let mark = match sign {
+                    Sign::Lt | Sign::Lte => "-",
+                    Sign::Gt | Sign::Gte => "+",
+                };
+                match self {
+                    Self::English => write!(
+                        f,
+                        "the left-hand side is {} 0 on the intervals marked {}{}",
+                        sign,
+                        mark,
+                        if *inclusive { ", roots included" } else { "" }
+                    ),
+                    Self::Korean => write!(
+                        f,
+                        "좌변은 부호가 {}인 구간에서 {} 0입니다{}",
+                        mark,
+                        sign,
+                        if *inclusive { " (근 포함)" } else { "" }
+                    ),
+                }
+            }
+            Step::CommonDenominator { function, sign } => match self {
+                Self::English => write!(f, "write as a single fraction: {} {} 0", function, sign),
+                Self::Korean => write!(
+                    f,
+                    "통분하여 하나의 분수로 나타냅니다: {} {} 0",
+                    function, sign
+                ),
+            },
+            Step::ExcludePoles { character, poles } => {
+                if poles.is_empty() {
+                    return match self {
+                        Self::English => write!(f, "the denominator is never 0"),
+                        Self::Korean => write!(f, "분모는 0이 되지 않습니다"),
+                    };
+                }
+                let mut points = String::new();
+                for (i, pole) in poles.iter().enumerate() {
+                    if i > 0 {
+                        points.push_str(", ");
+                    }
+                    points.push_str(&format!("{} = {}", character, pole));
+                }
+                match self {
+                    Self::English => write!(
+                        f,
+                        "the denominator is 0 at {}, which is left out of the answer",
+                        points
+                    ),
+                    Self::Korean => write!(f, "분모가 0이 되는 {}은(는) 답에서 제외합니다", points),
+                }
+            }
+            Step::NumeratorRoots { character, roots } => {
+                if roots.is_empty() {
+                    return match self {
+                        Self::English => write!(f, "the numerator is never 0"),
+                        Self::Korean => write!(f, "분자는 0이 되지 않습니다"),
+                    };
+                }
+                match self {
+                    Self::English => write!(f, "the numerator is 0 at ")?,
+                    Self::Korean => write!(f, "분자가 0이 되는 곳: ")?,
+                }
+                self.fmt_roots(f, character, roots)
+            }
+            Step::SplitCases { arguments } => {
+                let separator = match self {
+                    Self::English => " and ",
+                    Self::Korean => ", ",
+                };
+                let mut list = String::new();
+                for (i, argument) in arguments.iter().enumerate() {
+                    if i > 0 {
+                        list.push_str(separator);
+                    }
+                    list.push_str(&argument.to_string());
+                }
+                match self {
+                    Self::English => write!(f, "split into cases on the sign of {}", list),
+                    Self::Korean => write!(f, "{}의 부호에 따라 경우를 나눕니다", list),
+                }
+            }
+            Step::Case {
+                conditions,
+                inequality,
+                solution,
+            } => {
+                let mut when = String::new();
+                for (i, condition) in conditions.iter().enumerate() {
+                    if i > 0 {
+                        when.push_str(match self {
+                            Self::English => " and ",
+                            Self::Korean => ", ",
+                        });
+                    }
+                    when.push_str(&condition.to_string());
+                }
+                let solution = solution.localized(*self);
+                match (self, when.is_empty()) {
+                    (Self::English, true) => write!(f, "{}, so {}", inequality, solution),
+                    (Self::English, false) => write!(
+                        f,
+                        "when {} the inequality is {}, so {}",
+                        when, inequality, solution
+                    ),
+                    (Self::Korean, true) => write!(f, "{}이므로 {}", inequality, solution),
+                    (Self::Korean, false) => write!(
+                        f,
+                        "{}일 때 부등식은 {}이므로 {}",
+                        when, inequality, solution
+                    ),
+                }
+            }
+            Step::SplitChain { comparisons } => {
+                let separator = match self {
+                    Self::English => " and ",
+                    Self::Korean => ", ",
+                };
+                let mut list = String::new();
+                for (i, (left, sign, right)) in comparisons.iter().enumerate() {
+                    if i > 0 {
+                        list.push_str(separator);
+                    }
+                    list.push_str(&format!("{} {} {}", left, sign, right));
+                }
+                match self {
+                    Self::English => write!(
+                        f,
+                        "solve {} one at a time; the answer is where all of them hold",
+                        list
+                    ),
+                    Self::Korean => write!(f, "{}을(를) 각각 풀어 공통부분을 구합니다", list),
+                }
+            }
+            Step::Comparison {
+                left,
+                sign,
+                right,
+                solution,
+            } => match self {
+                Self::English => write!(
+                    f,
+                    "solution of {} {} {}: {}",
+                    left,
+                    sign,
+                    right,
+                    solution.localized(*self)
+                ),
+                Self::Korean => write!(
+                    f,
+                    "{} {} {}의 해: {}",
+                    left,
+                    sign,
+                    right,
+                    solution.localized(*self)
+                ),
+            },
+            Step::Answer(solution) => {
+                let label = match self {
+                    Self::English => "answer",
+                    Self::Korean => "답",
+                };
+                write!(f, "{}: {}", label, solution.localized(*self))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_locale() {
+        assert_eq!("en".parse(), Ok(Locale::English));
+        assert_eq!("ko-KR".parse(), Ok(Locale::Korean));
+        assert_eq!("ko_KR.UTF-8".parse(), Ok(Locale::Korean));
+        assert!("fr".parse::<Locale>().is_err());
+    }
+}
@@ -1,15 +1,310 @@
-use std::{env, process};
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, IsTerminal, Write},
+    path::PathBuf,
+    process,
+};
+
+use quadratic_inequality::{
+    chained::ChainedInequality,
+    error::Error,
+    explain, json,
+    latex::ToLatex,
+    locale::{Locale, Localize},
+    parse_and_solve,
+    solution::{SolutionSet, Symbols},
+};
+use rustyline::{error::ReadlineError, DefaultEditor};
+use serde_json::json;
+
+/// how the normalized problem and the answer are written out, one per line.
+#[derive(Clone, Copy)]
+enum Format {
+    /// `x < -5 OR x > 2`
+    Inequality,
+    /// `(-∞, -5) ∪ (2, ∞)`
+    Interval,
+    /// `(-inf, -5) U (2, inf)`
+    IntervalAscii,
+    /// `x^{2} + 3x - 10 > 0` and `(-\infty, -5) \cup (2, \infty)`
+    Latex,
+    /// a single JSON document, errors included; see [`quadratic_inequality::json`]
+    Json,
+}
+impl Format {
+    fn parse(format: &str) -> Result<Self, String> {
+        match format {
+            "inequality" => Ok(Self::Inequality),
+            "interval" => Ok(Self::Interval),
+            "interval-ascii" => Ok(Self::IntervalAscii),
+            "latex" => Ok(Self::Latex),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown format: {}", format)),
+        }
+    }
+    /// `input` solved, or what went wrong with it, written out. on its own that is
+    /// the normalized problem and the answer, or the error rendered under the input;
+    /// as line `line` of a batch it is a single line.
+    fn write(
+        &self,
+        input: &str,
+        solved: &Result<(ChainedInequality, SolutionSet), Error>,
+        locale: Locale,
+        line: Option<usize>,
+    ) -> String {
+        let (problem, answer) = match (self, solved) {
+            (Self::Json, solved) => {
+                let mut document = match solved {
+                    Ok((inequality, solution)) => json::solved(inequality, solution, locale),
+                    Err(e) => json::failed(e, locale),
+                };
+                match line {
+                    Some(line) => {
+                        document["line"] = line.into();
+                        return document.to_string();
+                    }
+                    None => return format!("{:#}", document),
+                }
+            }
+            (_, Err(e)) => {
+                return match line {
+                    Some(line) => format!("line {}: {}", line, e.localized(locale)),
+                    None => e.render_in(input, locale),
+                }
+            }
+            (Self::Inequality, Ok((inequality, solution))) => (
+                inequality.pretty(Symbols::Unicode).to_string(),
+                solution.localized(locale).to_string(),
+            ),
+            (Self::Interval, Ok((inequality, solution))) => (
+                inequality.pretty(Symbols::Unicode).to_string(),
+                solution.interval_notation(Symbols::Unicode).to_string(),
+            ),
+            (Self::IntervalAscii, Ok((inequality, solution))) => (
+                inequality.pretty(Symbols::Ascii).to_string(),
+                solution.interval_notation(Symbols::Ascii).to_string(),
+            ),
+            (Self::Latex, Ok((inequality, solution))) => {
+                (inequality.to_latex(), solution.to_latex())
+            }
+        };
+        match line {
+            Some(_) => answer,
+            None => format!("{}\n{}", problem, answer),
+        }
+    }
+}
+
+/// the language asked for with `--lang`, or else the one in `LANG`.
+fn locale(lang: Option<String>) -> Result<Locale, String> {
+    match lang {
+        Some(lang) => lang.parse(),
+        None => Ok(env::var("LANG")
+            .ok()
+            .and_then(|lang| lang.parse().ok())
+            .unwrap_or_default()),
+    }
+}
+
+fn exit_with(message: String) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+/// solve `input` and write it out in `format`, as line `line` of a batch if given.
+/// returns what to print and whether it was solved.
+fn run(input: &str, format: Format, locale: Locale, line: Option<usize>) -> (String, bool) {
+    let solved = parse_and_solve(input);
+    (format.write(input, &solved, locale, line), solved.is_ok())
+}
+
+/// solve every line of `lines`, writing one answer per line in the same order, or
+/// the line number and what went wrong. blank lines are skipped. returns whether
+/// every line was solved.
+fn run_batch(lines: impl BufRead, format: Format, locale: Locale) -> bool {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut succeeded = true;
+    for (i, line) in lines.lines().enumerate() {
+        let (written, solved) = match line {
+            Ok(line) if line.trim().is_empty() => continue,
+            // `trim_end` also drops the `\r` of a CRLF line ending
+            Ok(line) => run(line.trim_end(), format, locale, Some(i + 1)),
+            Err(e) => match format {
+                Format::Json => {
+                    let document = json!({
+                        "ok": false,
+                        "line": i + 1,
+                        "error": {"code": "unreadable_line", "message": e.to_string()},
+                    });
+                    (document.to_string(), false)
+                }
+                _ => (format!("line {}: {}", i + 1, e), false),
+            },
+        };
+        succeeded &= solved;
+        // the reader went away, so there is nobody left to tell
+        if writeln!(out, "{}", written).is_err() {
+            return false;
+        }
+    }
+    succeeded
+}
+
+const HELP: &str = "\
+type an inequality to solve it, or one of:
+  :explain             show (or stop showing) the solution step by step
+  :explain <problem>   solve one problem step by step
+  :format <format>     inequality, interval, interval-ascii, latex or json
+  :lang <language>     en or ko
+  :help                show this message
+  :quit                leave (so does Ctrl-D)";
+
+/// what the REPL commands have set.
+struct Session {
+    format: Format,
+    locale: Locale,
+    explain: bool,
+}
+impl Session {
+    /// the solution of `input`, step by step or in the current format, or what went
+    /// wrong.
+    fn solve(&self, input: &str, explain_steps: bool) -> String {
+        if !explain_steps {
+            return run(input, self.format, self.locale, None).0;
+        }
+        match explain(input) {
+            Ok(explanation) => explanation.localized(self.locale).to_string(),
+            Err(e) => e.render_in(input, self.locale),
+        }
+    }
+    /// carry out the command `command` (without the `:`), or say why not. returns
+    /// false for `:quit`.
+    fn command(&mut self, command: &str) -> bool {
+        let (name, argument) = match command.split_once(char::is_whitespace) {
+            Some((name, argument)) => (name, argument.trim()),
+            None => (command, ""),
+        };
+        match (name, argument) {
+            ("quit" | "q", _) => return false,
+            ("help", _) => println!("{}", HELP),
+            ("explain", "") => {
+                self.explain = !self.explain;
+                println!("explain: {}", if self.explain { "on" } else { "off" });
+            }
+            ("explain", input) => println!("{}", self.solve(input, true)),
+            ("format", format) => match Format::parse(format) {
+                Ok(format) => self.format = format,
+                Err(e) => println!("{}", e),
+            },
+            ("lang", lang) => match lang.parse() {
+                Ok(locale) => self.locale = locale,
+                Err(e) => println!("{}", e),
+            },
+            _ => println!("unknown command :{}, try :help", name),
+        }
+        true
+    }
+}
+
+/// where the REPL keeps its history between sessions, if there is a config directory.
+fn history_path() -> Option<PathBuf> {
+    let dir = dirs::config_dir()?.join("quadratic_inequality");
+    fs::create_dir_all(&dir).ok()?;
+    Some(dir.join("history.txt"))
+}
+
+/// read inequalities and commands from the terminal until `:quit` or Ctrl-D.
+fn repl(format: Format, locale: Locale) -> rustyline::Result<()> {
+    let mut editor = DefaultEditor::new()?;
+    let history = history_path();
+    if let Some(path) = &history {
+        // there is no history yet on the first run
+        let _ = editor.load_history(path);
+    }
+    let mut session = Session {
+        format,
+        locale,
+        explain: false,
+    };
+    println!("{}", HELP);
+    loop {
+        let line = match editor.readline("> ") {
+            Ok(line) => line,
+            // Ctrl-C abandons the line, as in a shell
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line)?;
+        match line.strip_prefix(':') {
+            Some(command) => {
+                if !session.command(command) {
+                    break;
+                }
+            }
+            None => println!("{}", session.solve(line, session.explain)),
+        }
+    }
+    if let Some(path) = &history {
+        editor.save_history(path)?;
+    }
+    Ok(())
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        eprintln!("only one argument needed: {}", args.join(", "));
-        process::exit(1);
+    let mut args = env::args().skip(1);
+    let (mut lang, mut format, mut file, mut inputs) = (None, None, None, Vec::new());
+    while let Some(arg) = args.next() {
+        // `--name value` or `--name=value`
+        let (name, value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => {
+                (name.to_string(), Some(value.to_string()))
+            }
+            _ => (arg.clone(), None),
+        };
+        match name.as_str() {
+            "--lang" => lang = value.or_else(|| args.next()),
+            "--format" => format = value.or_else(|| args.next()),
+            "--file" => file = value.or_else(|| args.next()),
+            _ => inputs.push(arg),
+        }
     }
-    let input = &args[1];
+    let locale = locale(lang).unwrap_or_else(|e| exit_with(e));
+    let format = format
+        .map_or(Ok(Format::Inequality), |format| Format::parse(&format))
+        .unwrap_or_else(|e| exit_with(e));
 
-    match quadratic_inequality::solve(input) {
-        Ok(res) => println!("{}", res),
-        Err(e) => eprintln!("{}", e),
+    // with no inequality given, read one per line from the file or stdin, or take
+    // them interactively from a terminal
+    let lines: Box<dyn BufRead> = match (file, inputs.len()) {
+        (Some(path), 0) => match File::open(&path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => exit_with(format!("cannot read {}: {}", path, e)),
+        },
+        (None, 0) if io::stdin().is_terminal() => {
+            if let Err(e) = repl(format, locale) {
+                exit_with(e.to_string());
+            }
+            return;
+        }
+        (None, 0) => Box::new(BufReader::new(io::stdin())),
+        (None, 1) => {
+            let (written, solved) = run(&inputs[0], format, locale, None);
+            match (solved, format) {
+                (true, _) | (false, Format::Json) => println!("{}", written),
+                (false, _) => eprintln!("{}", written),
+            }
+            process::exit(if solved { 0 } else { 1 });
+        }
+        _ => exit_with(format!("only one argument needed: {}", inputs.join(", "))),
+    };
+    if !run_batch(lines, format, locale) {
+        process::exit(1);
     }
 }
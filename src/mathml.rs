@@ -0,0 +1,427 @@
+use std::cmp::Ordering;
+
+use crate::{
+    absolute::{AbsoluteInequality, Expression},
+    chained::ChainedInequality,
+    polynomial::PolynomialInequality,
+    rational::{RationalFunction, RationalInequality},
+    real::Real,
+    solution::{Bound, SolutionSet},
+    surd::Surd,
+    types::{Number, Polynomial, Quadratic, QuadraticInequality, Sign},
+};
+
+/// something that can be written as MathML presentation markup, to go inside a
+/// `<math>` element.
+pub trait ToMathMl {
+    fn to_mathml(&self) -> String;
+}
+
+const MINUS: &str = "<mo>−</mo>";
+
+fn mn(n: impl ToString) -> String {
+    format!("<mn>{}</mn>", n.to_string())
+}
+/// `text` with the characters that mean something in markup replaced.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+fn mi(identifier: &str) -> String {
+    format!("<mi>{}</mi>", escape(identifier))
+}
+fn mrow(children: &str) -> String {
+    format!("<mrow>{}</mrow>", children)
+}
+fn mfrac(numerator: &str, denominator: &str) -> String {
+    format!("<mfrac>{}{}</mfrac>", numerator, denominator)
+}
+
+/// the absolute value of `number`, as `<mn>` or `<mfrac>`.
+fn magnitude(number: &Number) -> String {
+    let numerator = mn(number.numerator().unsigned_abs());
+    match number.denominator() {
+        1 => numerator,
+        d => mfrac(&numerator, &mn(d)),
+    }
+}
+
+impl ToMathMl for Number {
+    fn to_mathml(&self) -> String {
+        match self.signum() {
+            Ordering::Less => mrow(&format!("{}{}", MINUS, magnitude(self))),
+            _ => magnitude(self),
+        }
+    }
+}
+
+impl ToMathMl for Surd {
+    fn to_mathml(&self) -> String {
+        let (p, q, r, s) = self.parts();
+        let sign = |n: i64| if n < 0 { MINUS } else { "" };
+        if q == 0 {
+            let magnitude = match s {
+                1 => mn(p.unsigned_abs()),
+                _ => mfrac(&mn(p.unsigned_abs()), &mn(s)),
+            };
+            return if p < 0 {
+                mrow(&format!("{}{}", MINUS, magnitude))
+            } else {
+                magnitude
+            };
+        }
+        let radical = match q.unsigned_abs() {
+            1 => format!("<msqrt>{}</msqrt>", mn(r)),
+            k => mrow(&format!("{}<msqrt>{}</msqrt>", mn(k), mn(r))),
+        };
+        let numerator = match p {
+            0 => radical,
+            _ => mrow(&format!(
+                "{}{}<mo>{}</mo>{}",
+                sign(p),
+                mn(p.unsigned_abs()),
+                if q < 0 { "−" } else { "+" },
+                radical
+            )),
+        };
+        match (p, s) {
+            (0, 1) => mrow(&format!("{}{}", sign(q), numerator)),
+            (0, _) => mrow(&format!("{}{}", sign(q), mfrac(&numerator, &mn(s)))),
+            (_, 1) => numerator,
+            _ => mfrac(&numerator, &mn(s)),
+        }
+    }
+}
+
+impl ToMathMl for Real {
+    fn to_mathml(&self) -> String {
+        match self {
+            Self::Exact(value) => value.to_mathml(),
+            Self::Approx(value) => mrow(&format!("<mo>≈</mo>{}", mn(Real::rounded(*value)))),
+        }
+    }
+}
+
+impl ToMathMl for Polynomial {
+    fn to_mathml(&self) -> String {
+        let mut terms = String::new();
+        for (coefficient, power) in self.terms() {
+            let negative = coefficient.signum() == Ordering::Less;
+            match (terms.is_empty(), negative) {
+                (_, true) => terms.push_str(MINUS),
+                (false, false) => terms.push_str("<mo>+</mo>"),
+                (true, false) => {}
+            }
+            if (
+                coefficient.numerator().unsigned_abs(),
+                coefficient.denominator(),
+            ) != (1, 1)
+                || power == 0
+            {
+                terms.push_str(&magnitude(&coefficient));
+                if power > 0 {
+                    terms.push_str("<mo>&#x2062;</mo>");
+                }
+            }
+            match power {
+                0 => {}
+                1 => terms.push_str(&mi(self.character())),
+                n => terms.push_str(&format!("<msup>{}{}</msup>", mi(self.character()), mn(n))),
+            }
+        }
+        if terms.is_empty() {
+            terms = mn(0);
+        }
+        mrow(&terms)
+    }
+}
+
+impl ToMathMl for Quadratic {
+    fn to_mathml(&self) -> String {
+        Polynomial::from(self).to_mathml()
+    }
+}
+
+impl ToMathMl for RationalFunction {
+    fn to_mathml(&self) -> String {
+        if self.is_polynomial() {
+            return self.numerator().to_mathml();
+        }
+        mfrac(
+            &self.numerator().to_mathml(),
+            &self.denominator().to_mathml(),
+        )
+    }
+}
+
+impl ToMathMl for Expression {
+    fn to_mathml(&self) -> String {
+        let operand = |operand: &Self, precedence: u8| {
+            if operand.precedence() < precedence {
+                mrow(&format!("<mo>(</mo>{}<mo>)</mo>", operand.to_mathml()))
+            } else {
+                operand.to_mathml()
+            }
+        };
+        match self {
+            Self::Function(function) => function.to_mathml(),
+            Self::Abs(argument) => mrow(&format!("<mo>|</mo>{}<mo>|</mo>", argument.to_mathml())),
+            Self::Neg(negated) => mrow(&format!("{}{}", MINUS, operand(negated, 1))),
+            Self::Add(left, right) => match right.split_sign() {
+                (true, right) => mrow(&format!(
+                    "{}{}{}",
+                    left.to_mathml(),
+                    MINUS,
+                    operand(&right, 1)
+                )),
+                (false, right) => mrow(&format!(
+                    "{}<mo>+</mo>{}",
+                    left.to_mathml(),
+                    right.to_mathml()
+                )),
+            },
+            Self::Mul(left, right) => {
+                // a visible dot before a number or product, which would otherwise run on
+                let times = match right.as_ref() {
+                    Self::Function(_) if right.precedence() >= 2 => "·",
+                    _ => "&#x2062;",
+                };
+                mrow(&format!(
+                    "{}<mo>{}</mo>{}",
+                    operand(left, 1),
+                    times,
+                    operand(right, 2)
+                ))
+            }
+            Self::Div(left, right) => mfrac(&left.to_mathml(), &right.to_mathml()),
+            Self::Pow(base, exponent) => {
+                format!("<msup>{}{}</msup>", operand(base, 3), mn(exponent))
+            }
+        }
+    }
+}
+
+impl ToMathMl for Sign {
+    fn to_mathml(&self) -> String {
+        let sign = match self {
+            Self::Lt => "&lt;",
+            Self::Lte => "≤",
+            Self::Gt => "&gt;",
+            Self::Gte => "≥",
+        };
+        format!("<mo>{}</mo>", sign)
+    }
+}
+
+/// the normalized form `ax² + bx + c < 0`.
+impl ToMathMl for QuadraticInequality {
+    fn to_mathml(&self) -> String {
+        mrow(&format!(
+            "{}{}{}",
+            self.quadratic().to_mathml(),
+            self.sign().to_mathml(),
+            mn(0)
+        ))
+    }
+}
+
+/// the normalized form `x³ − 4x > 0`.
+impl ToMathMl for PolynomialInequality {
+    fn to_mathml(&self) -> String {
+        mrow(&format!(
+            "{}{}{}",
+            self.polynomial().to_mathml(),
+            self.sign().to_mathml(),
+            mn(0)
+        ))
+    }
+}
+
+/// the normalized form `(x − 1)/(x + 2) ≥ 0`, as a fraction.
+impl ToMathMl for RationalInequality {
+    fn to_mathml(&self) -> String {
+        mrow(&format!(
+            "{}{}{}",
+            self.function().to_mathml(),
+            self.sign().to_mathml(),
+            mn(0)
+        ))
+    }
+}
+
+/// the normalized form `|x − 3| − 2 < 0`.
+impl ToMathMl for AbsoluteInequality {
+    fn to_mathml(&self) -> String {
+        mrow(&format!(
+            "{}{}{}",
+            self.expression().to_mathml(),
+            self.sign().to_mathml(),
+            mn(0)
+        ))
+    }
+}
+
+/// the chain as written, `−3 < x² − 4x < 5`, or the normalized form of a single
+/// comparison.
+impl ToMathMl for ChainedInequality {
+    fn to_mathml(&self) -> String {
+        if let [inequality] = self.inequalities() {
+            return inequality.to_mathml();
+        }
+        let mut mathml = self.sides()[0].to_mathml();
+        for (sign, side) in self.signs().iter().zip(&self.sides()[1..]) {
+            mathml.push_str(&sign.to_mathml());
+            mathml.push_str(&side.to_mathml());
+        }
+        mrow(&mathml)
+    }
+}
+
+/// interval notation: `(−∞, −5) ∪ (2, ∞)`, `∅`, `{3}`.
+impl ToMathMl for SolutionSet {
+    fn to_mathml(&self) -> String {
+        if self.is_empty() {
+            return "<mi>∅</mi>".to_string();
+        }
+        let intervals: Vec<String> = self
+            .intervals()
+            .iter()
+            .map(|interval| match (interval.lower(), interval.upper()) {
+                (Bound::Closed(l), Bound::Closed(u)) if l == u => {
+                    format!("<mo>{{</mo>{}<mo>}}</mo>", l.to_mathml())
+                }
+                (lower, upper) => {
+                    let lower = match lower {
+                        Bound::Unbounded => format!("<mo>(</mo>{}<mi>∞</mi>", MINUS),
+                        Bound::Open(l) => format!("<mo>(</mo>{}", l.to_mathml()),
+                        Bound::Closed(l) => format!("<mo>[</mo>{}", l.to_mathml()),
+                    };
+                    let upper = match upper {
+                        Bound::Unbounded => "<mi>∞</mi><mo>)</mo>".to_string(),
+                        Bound::Open(u) => format!("{}<mo>)</mo>", u.to_mathml()),
+                        Bound::Closed(u) => format!("{}<mo>]</mo>", u.to_mathml()),
+                    };
+                    format!("{}<mo>,</mo>{}", lower, upper)
+                }
+            })
+            .collect();
+        mrow(&intervals.join("<mo>∪</mo>"))
+    }
+}
+
+/// a whole `<math>` element: the inequality, then `x ∈` the solution set.
+pub fn problem_and_answer(inequality: &ChainedInequality, solution: &SolutionSet) -> String {
+    format!(
+        "<math display=\"block\">{}<mo>⇔</mo>{}<mo>∈</mo>{}</math>",
+        inequality.to_mathml(),
+        mi(solution.character()),
+        solution.to_mathml()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Interval;
+
+    #[test]
+    fn surd_to_mathml() {
+        assert_eq!(
+            Surd::integer(-4).to_mathml(),
+            "<mrow><mo>−</mo><mn>4</mn></mrow>"
+        );
+        assert_eq!(
+            Surd::new(0, 3, 2, 2).to_mathml(),
+            "<mrow><mfrac><mrow><mn>3</mn><msqrt><mn>2</mn></msqrt></mrow><mn>2</mn></mfrac></mrow>"
+        );
+        assert_eq!(
+            Surd::new(-3, -1, 5, 2).to_mathml(),
+            "<mfrac><mrow><mo>−</mo><mn>3</mn><mo>−</mo><msqrt><mn>5</mn></msqrt></mrow><mn>2</mn></mfrac>"
+        );
+    }
+
+    #[test]
+    fn quadratic_inequality_to_mathml() {
+        let quad_ineq =
+            QuadraticInequality::new(Quadratic::new("x".to_string(), 1, -3, 2), Sign::Lt);
+        assert_eq!(
+            quad_ineq.to_mathml(),
+            "<mrow><mrow><msup><mi>x</mi><mn>2</mn></msup><mo>−</mo><mn>3</mn><mo>&#x2062;</mo>\
+             <mi>x</mi><mo>+</mo><mn>2</mn></mrow><mo>&lt;</mo><mn>0</mn></mrow>"
+        );
+    }
+
+    #[test]
+    fn polynomial_inequality_to_mathml() {
+        let inequality =
+            PolynomialInequality::new(Polynomial::new("x", vec![0, -4, 0, 1]), Sign::Gt);
+        assert_eq!(
+            inequality.to_mathml(),
+            "<mrow><mrow><msup><mi>x</mi><mn>3</mn></msup><mo>−</mo><mn>4</mn><mo>&#x2062;</mo>\
+             <mi>x</mi></mrow><mo>&gt;</mo><mn>0</mn></mrow>"
+        );
+    }
+
+    #[test]
+    fn rational_inequality_to_mathml() {
+        let function = RationalFunction::new(
+            Polynomial::new("x", vec![1]),
+            Polynomial::new("x", vec![0, 2]),
+        )
+        .unwrap();
+        assert_eq!(
+            RationalInequality::new(function, Sign::Gte).to_mathml(),
+            "<mrow><mfrac><mrow><mn>1</mn></mrow><mrow><mn>2</mn><mo>&#x2062;</mo><mi>x</mi>\
+             </mrow></mfrac><mo>≥</mo><mn>0</mn></mrow>"
+        );
+    }
+
+    #[test]
+    fn absolute_inequality_to_mathml() {
+        assert_eq!(
+            crate::parser::parse_absolute("|x-3|<2")
+                .unwrap()
+                .to_mathml(),
+            "<mrow><mrow><mrow><mo>|</mo><mrow><mi>x</mi><mo>−</mo><mn>3</mn></mrow><mo>|</mo>\
+             </mrow><mo>−</mo><mrow><mn>2</mn></mrow></mrow><mo>&lt;</mo><mn>0</mn></mrow>"
+        ); // a number after an absolute value is set off by a visible dot
+        assert_eq!(
+            crate::parser::parse_absolute("|x|*2>1")
+                .unwrap()
+                .to_mathml(),
+            "<mrow><mrow><mrow><mrow><mo>|</mo><mrow><mi>x</mi></mrow><mo>|</mo></mrow><mo>·</mo>\
+             <mrow><mn>2</mn></mrow></mrow><mo>−</mo><mrow><mn>1</mn></mrow></mrow><mo>&gt;</mo>\
+             <mn>0</mn></mrow>"
+        );
+    }
+
+    #[test]
+    fn chained_inequality_to_mathml() {
+        assert_eq!(
+            crate::parser::parse_chained("1 < x < 2")
+                .unwrap()
+                .to_mathml(),
+            "<mrow><mrow><mn>1</mn></mrow><mo>&lt;</mo><mrow><mi>x</mi></mrow><mo>&lt;</mo>\
+             <mrow><mn>2</mn></mrow></mrow>"
+        );
+    }
+
+    #[test]
+    fn solution_set_to_mathml() {
+        assert_eq!(SolutionSet::empty("x").to_mathml(), "<mi>∅</mi>");
+        assert_eq!(
+            SolutionSet::from_intervals(
+                "x",
+                vec![
+                    Interval::new(Bound::Unbounded, Bound::Open(Surd::integer(1).into())),
+                    Interval::point(Surd::integer(2).into()),
+                ]
+            )
+            .to_mathml(),
+            "<mrow><mo>(</mo><mo>−</mo><mi>∞</mi><mo>,</mo><mn>1</mn><mo>)</mo>\
+             <mo>∪</mo><mo>{</mo><mn>2</mn><mo>}</mo></mrow>"
+        );
+    }
+}
@@ -1,78 +1,457 @@
+use std::str::FromStr;
+
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while1},
+    bytes::complete::{tag, take_while, take_while1},
     character::complete::{alpha1, char, digit1},
-    combinator::{map, map_res, not, opt},
-    multi::many1,
-    sequence::{preceded, tuple},
+    combinator::{consumed, cut, map, not, opt, peek},
+    error::{context, ContextError, ErrorKind as NomErrorKind, ParseError},
+    sequence::{delimited, preceded, separated_pair, terminated, tuple},
+    Offset,
 };
 
 use crate::{
-    error::{Error, Result},
-    types::{Monomial, Number, Quadratic, QuadraticInequality, Sign},
+    absolute::{AbsoluteInequality, Expression},
+    chained::ChainedInequality,
+    error::{Error, ErrorKind, Expected, Result, Span},
+    polynomial::PolynomialInequality,
+    rational::{RationalFunction, RationalInequality},
+    types::{Number, Polynomial, Quadratic, QuadraticInequality, Sign},
 };
 
-type IResult<'a, I, O> = nom::IResult<I, O, Error<'a>>;
+/// a parse error, together with the slice of the input it is about.
+#[derive(Debug, PartialEq)]
+struct ErrorAt<'a> {
+    kind: ErrorKind,
+    at: &'a str,
+}
+impl<'a> ErrorAt<'a> {
+    fn new(kind: ErrorKind, at: &'a str) -> Self {
+        Self {
+            kind,
+            at: at.trim(),
+        }
+    }
+    /// resolve the slice into a span of `input`, which it must be part of.
+    fn into_error(self, input: &str) -> Error {
+        let start = input.offset(self.at);
+        let kind = match self.kind {
+            ErrorKind::Syntax {
+                expected,
+                after: None,
+            } => ErrorKind::Syntax {
+                expected,
+                after: last_operator(&input[..start]).map(str::to_string),
+            },
+            kind => kind,
+        };
+        Error::new(kind, Span::new(start, start + self.at.len()))
+    }
+}
+impl<'a> ParseError<&'a str> for ErrorAt<'a> {
+    fn from_error_kind(input: &'a str, _: NomErrorKind) -> Self {
+        Self::new(
+            ErrorKind::Syntax {
+                expected: None,
+                after: None,
+            },
+            next_token(input),
+        )
+    }
+    fn append(_: &'a str, _: NomErrorKind, other: Self) -> Self {
+        other
+    }
+}
+impl<'a> ContextError<&'a str> for ErrorAt<'a> {
+    /// only the innermost context is kept, as it says the most about what went wrong.
+    fn add_context(_: &'a str, ctx: &'static str, mut other: Self) -> Self {
+        if let ErrorKind::Syntax {
+            expected: expected @ None,
+            ..
+        } = &mut other.kind
+        {
+            *expected = match ctx {
+                OPERAND => Some(Expected::Operand),
+                INEQUALITY_SIGN => Some(Expected::InequalitySign),
+                EXPONENT => Some(Expected::Exponent),
+                CLOSING_PARENTHESIS => Some(Expected::ClosingParenthesis),
+                CLOSING_BAR => Some(Expected::ClosingBar),
+                _ => None,
+            };
+        }
+        other
+    }
+}
+
+type IResult<'a, O> = nom::IResult<&'a str, O, ErrorAt<'a>>;
+
+// names for `context`, each standing for one kind of `Expected`
+const OPERAND: &str = "operand";
+const INEQUALITY_SIGN: &str = "inequality sign";
+const EXPONENT: &str = "exponent";
+const CLOSING_PARENTHESIS: &str = "closing parenthesis";
+const CLOSING_BAR: &str = "closing bar";
+/// how deep parentheses may nest. the parser recurses once per level, so this keeps
+/// hostile input from overflowing the stack.
+const MAX_NESTING: usize = 32;
+/// how many absolute values an inequality may hold. each one can double the number
+/// of cases to solve.
+const MAX_ABSOLUTE_VALUES: usize = 8;
 
-fn plus_minus(input: &str) -> IResult<&str, &str> {
-    map(opt(alt((tag("+"), tag("-")))), |s| s.unwrap_or("+"))(input)
+fn is_sign_char(c: char) -> bool {
+    "<>=≤≥".contains(c)
 }
-fn coefficient(input: &str) -> IResult<&str, Number> {
-    map(take_while1(|c| matches!(c, '0'..='9')), |s: &str| {
-        Number::new_with_default(Some(s), 1)
-    })(input)
+/// the token at the start of `input`, for pointing at what could not be parsed.
+fn next_token(input: &str) -> &str {
+    let input = input.trim_start();
+    let len = match input.chars().next() {
+        None => 0,
+        Some(c) if c.is_alphanumeric() => input
+            .find(|c: char| !c.is_alphanumeric() && c != '.')
+            .unwrap_or(input.len()),
+        Some(c) if is_sign_char(c) => input.find(|c| !is_sign_char(c)).unwrap_or(input.len()),
+        Some(c) => c.len_utf8(),
+    };
+    &input[..len]
+}
+/// the operator at the end of `input`, if any, to say what the missing part should follow.
+fn last_operator(input: &str) -> Option<&str> {
+    let input = input.trim_end();
+    let sign = input
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| is_sign_char(c))
+        .last();
+    match (sign, input.chars().last()) {
+        (Some((i, _)), _) => Some(&input[i..]),
+        (None, Some(c)) if "+-*/^(".contains(c) => Some(&input[input.len() - c.len_utf8()..]),
+        _ => None,
+    }
 }
-fn character(input: &str) -> IResult<&str, &str> {
-    alpha1(input)
+
+/// any run of whitespace, including tabs and non-breaking spaces.
+fn space(input: &str) -> IResult<'_, &str> {
+    take_while(char::is_whitespace)(input)
 }
-fn degree(input: &str) -> IResult<&str, Number> {
-    map(opt(preceded(char('^'), digit1)), |s| {
-        Number::new_with_default(s, 1)
+/// `inner`, optionally preceded by whitespace.
+fn token<'a, O, F>(inner: F) -> impl FnMut(&'a str) -> IResult<'a, O>
+where
+    F: FnMut(&'a str) -> IResult<'a, O>,
+{
+    preceded(space, inner)
+}
+
+fn plus_minus(input: &str) -> IResult<'_, &str> {
+    token(alt((tag("+"), tag("-"))))(input)
+}
+/// like `map_res`, but a failed conversion is a hard failure pointing at the input
+/// `parser` recognized: trying other alternatives would only hide the real problem.
+fn convert<'a, I, O, F, G>(mut parser: F, mut f: G) -> impl FnMut(&'a str) -> IResult<'a, O>
+where
+    F: FnMut(&'a str) -> IResult<'a, I>,
+    G: FnMut(I) -> std::result::Result<O, ErrorKind>,
+{
+    move |input| {
+        let (rest, value) = parser(input)?;
+        f(value)
+            .map(|output| (rest, output))
+            .map_err(|kind| nom::Err::Failure(ErrorAt::new(kind, &input[..input.offset(rest)])))
+    }
+}
+/// `first` followed by any number of `next`, combined from left to right with `f`.
+/// a failed combination points at the `next` that caused it.
+fn fold<'a, O, P, F, G, H>(
+    mut first: F,
+    mut next: G,
+    mut f: H,
+) -> impl FnMut(&'a str) -> IResult<'a, O>
+where
+    F: FnMut(&'a str) -> IResult<'a, O>,
+    G: FnMut(&'a str) -> IResult<'a, P>,
+    H: FnMut(O, P) -> std::result::Result<O, ErrorKind>,
+{
+    move |input| {
+        let (mut rest, mut acc) = first(input)?;
+        loop {
+            match next(rest) {
+                Ok((after, _)) if after.len() == rest.len() => return Ok((rest, acc)),
+                Ok((after, item)) => {
+                    acc = f(acc, item).map_err(|kind| {
+                        nom::Err::Failure(ErrorAt::new(kind, &rest[..rest.offset(after)]))
+                    })?;
+                    rest = after;
+                }
+                Err(nom::Err::Error(_)) => return Ok((rest, acc)),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+/// digits read as a non-negative integer.
+fn natural<T: FromStr>(input: &str) -> IResult<'_, T> {
+    convert(digit1, |digits: &str| {
+        digits.parse().map_err(|_| ErrorKind::Overflow)
     })(input)
 }
-fn coefficient_character(input: &str) -> IResult<&str, (Number, Option<&str>)> {
+/// a whole number, a decimal like `0.5` or a fraction like `3/4`.
+fn coefficient(input: &str) -> IResult<'_, Number> {
+    token(alt((
+        convert(
+            // a decimal denominator is left to division, so `1/2.5` is not read as `1/2`
+            separated_pair(natural, char('/'), terminated(natural, not(char('.')))),
+            |(numerator, denominator)| Number::from_fraction(numerator, denominator),
+        ),
+        convert(
+            tuple((digit1, opt(preceded(char('.'), digit1)))),
+            |(integer, fraction)| Number::from_decimal(integer, fraction.unwrap_or("")),
+        ),
+    )))(input)
+}
+fn character(input: &str) -> IResult<'_, &str> {
+    token(alpha1)(input)
+}
+fn degree(input: &str) -> IResult<'_, u32> {
+    map(
+        opt(preceded(
+            token(char('^')),
+            cut(context(EXPONENT, token(natural))),
+        )),
+        |n| n.unwrap_or(1),
+    )(input)
+}
+
+/// a number, a variable, or an expression in parentheses or between `|` bars.
+/// `BARS` is set between bars, where a `|` after an operand always closes them.
+fn atom<const BARS: bool>(input: &str) -> IResult<'_, Expression> {
     alt((
-        tuple((coefficient, map(not(character), |_| None))),
-        map(tuple((opt(coefficient), character)), |(n, s)| {
-            (n.unwrap_or_else(|| Number::new(1)), Some(s))
-        }),
+        map(coefficient, |c| Polynomial::constant(c).into()),
+        map(character, |c| Polynomial::variable(c).into()),
+        delimited(
+            token(char('(')),
+            cut(context(OPERAND, expression::<false>)),
+            cut(context(CLOSING_PARENTHESIS, token(char(')')))),
+        ),
+        convert(
+            delimited(
+                token(char('|')),
+                cut(context(OPERAND, expression::<true>)),
+                cut(context(CLOSING_BAR, token(char('|')))),
+            ),
+            Expression::abs,
+        ),
     ))(input)
 }
-
-fn monomial(input: &str) -> IResult<&str, Monomial> {
-    map(
-        tuple((plus_minus, coefficient_character, degree)),
-        |(plus_minus, (mut coefficient, character), degree)| {
-            coefficient.set_sign(plus_minus);
-            Monomial::new(coefficient, character, degree)
+fn power<const BARS: bool>(input: &str) -> IResult<'_, Expression> {
+    convert(tuple((atom::<BARS>, degree)), |(base, exponent)| {
+        base.pow(exponent)
+    })(input)
+}
+/// a power written right after another, as in `5|x|` or `2(x + 1)`. it may not start
+/// with a number, so that `1 2` is not read as `2`, and between bars it may not start
+/// with `|`, so that `||x| - 1|` reads the way it looks. after a space it must be in
+/// parentheses, so that `x^2 > 1 x` is not read as `x^2 > x`.
+fn juxtaposed<const BARS: bool>(input: &str) -> IResult<'_, Expression> {
+    let (input, _) = not(token(digit1))(input)?;
+    if input.starts_with(char::is_whitespace) {
+        peek(token(char('(')))(input)?;
+    }
+    if BARS {
+        preceded(not(token(char('|'))), power::<BARS>)(input)
+    } else {
+        power::<BARS>(input)
+    }
+}
+type Operation = fn(Expression, Expression) -> std::result::Result<Expression, ErrorKind>;
+/// powers multiplied together, either with `*` or written next to each other, or
+/// divided with `/`, from left to right.
+fn term<const BARS: bool>(input: &str) -> IResult<'_, Expression> {
+    fold(
+        power::<BARS>,
+        alt((
+            map(
+                preceded(token(char('*')), cut(context(OPERAND, power::<BARS>))),
+                |power| (Expression::mul as Operation, power),
+            ),
+            map(
+                preceded(token(char('/')), cut(context(OPERAND, power::<BARS>))),
+                |power| (Expression::div as Operation, power),
+            ),
+            map(juxtaposed::<BARS>, |power| {
+                (Expression::mul as Operation, power)
+            }),
+        )),
+        |term, (operation, power)| operation(term, power),
+    )(input)
+}
+fn signed_term<const BARS: bool>(input: &str) -> IResult<'_, Expression> {
+    convert(
+        tuple((plus_minus, cut(context(OPERAND, term::<BARS>)))),
+        |(sign, term)| match sign {
+            "-" => term.negate(),
+            _ => Ok(term),
         },
     )(input)
 }
+fn expression<const BARS: bool>(input: &str) -> IResult<'_, Expression> {
+    fold(
+        alt((signed_term::<BARS>, term::<BARS>)),
+        signed_term::<BARS>,
+        Expression::add,
+    )(input)
+}
 
-fn quadratic(input: &str) -> IResult<&str, Quadratic> {
-    map_res(many1(monomial), Quadratic::from_monomials)(input)
+fn absolute(input: &str) -> IResult<'_, Expression> {
+    context(OPERAND, expression::<false>)(input)
+}
+fn rational(input: &str) -> IResult<'_, RationalFunction> {
+    convert(absolute, Expression::into_function)(input)
+}
+fn polynomial(input: &str) -> IResult<'_, Polynomial> {
+    convert(absolute, |expression| {
+        expression
+            .into_function()
+            .and_then(RationalFunction::into_polynomial)
+            .map_err(|_| ErrorKind::InvalidPolynomial)
+    })(input)
+}
+fn quadratic(input: &str) -> IResult<'_, Quadratic> {
+    convert(polynomial, Quadratic::from_polynomial)(input)
 }
 
-fn sign(input: &str) -> IResult<&str, Sign> {
-    map_res(
-        alt((tag("<="), tag("≤"), tag("<"), tag(">="), tag("≥"), tag(">"))),
-        Sign::new,
-    )(input)
+/// a run of sign characters, which must make up one inequality sign: `=` or `<<`
+/// is read whole and then rejected.
+fn sign(input: &str) -> IResult<'_, Sign> {
+    convert(token(take_while1(is_sign_char)), Sign::new)(input)
 }
 
-fn quadratic_inequality(input: &str) -> IResult<&str, QuadraticInequality> {
-    map(
-        tuple((quadratic, sign, quadratic)),
-        QuadraticInequality::from_expr,
-    )(input)
+/// both sides of an inequality, each read by `side`, combined by `build`.
+fn inequality<'a, S, O, F>(
+    side: fn(&'a str) -> IResult<'a, S>,
+    mut build: F,
+) -> impl FnMut(&'a str) -> IResult<'a, O>
+where
+    F: FnMut((S, Sign, S)) -> std::result::Result<O, ErrorKind>,
+{
+    move |input| {
+        let (rest, (left, sign, (right_input, right))) = tuple((
+            side,
+            cut(context(INEQUALITY_SIGN, sign)),
+            cut(consumed(side)),
+        ))(input)?;
+        // the left side decides the variable, so a mismatch is the right side's fault
+        let output = build((left, sign, right))
+            .map_err(|kind| nom::Err::Failure(ErrorAt::new(kind, right_input)))?;
+        let (rest, _) = space(rest)?;
+        Ok((rest, output))
+    }
+}
+fn quadratic_inequality(input: &str) -> IResult<'_, QuadraticInequality> {
+    inequality(quadratic, QuadraticInequality::from_expr)(input)
+}
+fn polynomial_inequality(input: &str) -> IResult<'_, PolynomialInequality> {
+    inequality(polynomial, PolynomialInequality::from_expr)(input)
+}
+fn rational_inequality(input: &str) -> IResult<'_, RationalInequality> {
+    inequality(rational, RationalInequality::from_expr)(input)
+}
+fn absolute_inequality(input: &str) -> IResult<'_, AbsoluteInequality> {
+    inequality(absolute, AbsoluteInequality::from_expr)(input)
+}
+/// an inequality, then any number of further comparisons, each with the side before.
+fn chained_inequality(input: &str) -> IResult<'_, ChainedInequality> {
+    let (mut input, mut chain) = inequality(absolute, ChainedInequality::from_expr)(input)?;
+    while let (rest, Some((sign, (right_input, right)))) =
+        opt(tuple((sign, cut(consumed(absolute)))))(input)?
+    {
+        chain = chain
+            .then(sign, right)
+            .map_err(|kind| nom::Err::Failure(ErrorAt::new(kind, right_input)))?;
+        input = space(rest)?.0;
+    }
+    Ok((input, chain))
+}
+
+/// the first opening parenthesis nested more than [`MAX_NESTING`] deep.
+fn too_deep(input: &str) -> Option<&str> {
+    let mut depth = 0_usize;
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' if depth == MAX_NESTING => return Some(&input[i..i + 1]),
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// turn the outcome of a parser run over the whole of `input` into a result,
+/// whatever way it went.
+fn finish<O>(input: &str, result: IResult<'_, O>) -> Result<O> {
+    match result {
+        Ok(("", output)) => Ok(output),
+        Ok((rest, _)) => Err(ErrorAt::new(
+            ErrorKind::TrailingInput {
+                found: rest.trim_end().to_string(),
+            },
+            rest,
+        )
+        .into_error(input)),
+        Err(nom::Err::Error(error)) | Err(nom::Err::Failure(error)) => Err(error.into_error(input)),
+        Err(nom::Err::Incomplete(_)) => Err(Error::new(
+            ErrorKind::UnexpectedEnd,
+            Span::new(input.len(), input.len()),
+        )),
+    }
+}
+
+/// the first `|` beyond those of [`MAX_ABSOLUTE_VALUES`] absolute values.
+fn too_many_bars(input: &str) -> Option<&str> {
+    let (i, _) = input.match_indices('|').nth(2 * MAX_ABSOLUTE_VALUES)?;
+    Some(&input[i..i + 1])
 }
 
-pub(crate) fn parse(input: &str) -> Result<QuadraticInequality> {
-    match quadratic_inequality(input) {
-        Ok(quad_ineq) => Ok(quad_ineq.1),
-        Err(nom::Err::Error(error)) => Err(error),
-        _ => unreachable!(),
+/// run `parser` over the whole of `input`, unless parentheses nest too deep or there
+/// are too many absolute values.
+fn parse_with<'a, O>(input: &'a str, parser: impl FnMut(&'a str) -> IResult<'a, O>) -> Result<O> {
+    if let Some(paren) = too_deep(input) {
+        return Err(ErrorAt::new(ErrorKind::TooDeeplyNested, paren).into_error(input));
+    }
+    if let Some(bar) = too_many_bars(input) {
+        return Err(ErrorAt::new(ErrorKind::TooManyAbsoluteValues, bar).into_error(input));
     }
+    let mut parser = parser;
+    finish(input, parser(input))
+}
+
+/// read `input` as a quadratic inequality with everything moved to the left-hand side.
+pub fn parse(input: &str) -> Result<QuadraticInequality> {
+    parse_with(input, quadratic_inequality)
+}
+
+/// read `input` as a polynomial inequality of any degree, with everything moved to
+/// the left-hand side.
+pub fn parse_polynomial(input: &str) -> Result<PolynomialInequality> {
+    parse_with(input, polynomial_inequality)
+}
+
+/// read `input` as an inequality between fractions of polynomials, such as
+/// `x/(x-3) < 2`, with everything moved to the left-hand side over a common
+/// denominator.
+pub fn parse_rational(input: &str) -> Result<RationalInequality> {
+    parse_with(input, rational_inequality)
+}
+
+/// read `input` as an inequality that may hold absolute values, such as
+/// `x^2 - 5|x| + 6 < 0`, with everything moved to the left-hand side.
+pub fn parse_absolute(input: &str) -> Result<AbsoluteInequality> {
+    parse_with(input, absolute_inequality)
+}
+
+/// read `input` as one or more comparisons in a row, such as `-3 < x^2-4x < 5`,
+/// keeping the sides as written.
+pub fn parse_chained(input: &str) -> Result<ChainedInequality> {
+    parse_with(input, chained_inequality)
 }
 
 #[cfg(test)]
@@ -83,39 +462,74 @@ mod tests {
 
     #[test]
     fn parse_degree() {
-        assert_eq!(degree("^3"), Ok(("", Number::new(3))))
+        assert_eq!(degree("^3"), Ok(("", 3)))
     }
 
     #[test]
-    fn parse_monomial() {
+    fn parse_coefficient() {
+        assert_eq!(coefficient("12"), Ok(("", Number::new(12, 1))));
+        assert_eq!(coefficient("0.25"), Ok(("", Number::new(1, 4))));
+        assert_eq!(coefficient("3/4x"), Ok(("x", Number::new(3, 4))));
+        assert_eq!(coefficient("1/2.5"), Ok(("/2.5", Number::new(1, 1))));
+        assert_eq!(parse_chained("x^2 > 1/2.5"), parse_chained("x^2 > 0.4"));
+        assert_eq!(parse_chained("1/2.5x > 1"), parse_chained("0.4x > 1"));
         assert_eq!(
-            monomial("+x^2"),
-            Ok(("", Monomial::new(Number::new(1), Some("x"), Number::new(2))))
+            coefficient("1/0"),
+            Err(nom::Err::Failure(ErrorAt::new(
+                ErrorKind::DivisionByZero,
+                "1/0"
+            )))
         );
+    }
+
+    #[test]
+    fn parse_term() {
         assert_eq!(
-            monomial("-x^4"),
-            Ok((
-                "",
-                Monomial::new(Number::new(-1), Some("x"), Number::new(4))
-            ))
+            expression::<false>("+x^2"),
+            Ok(("", Polynomial::new("x", vec![0, 0, 1]).into()))
+        );
+        assert_eq!(
+            expression::<false>("-x^4"),
+            Ok(("", Polynomial::new("x", vec![0, 0, 0, 0, -1]).into()))
         );
     }
 
     #[test]
-    fn parse_monomial_only_variable() {
+    fn parse_term_only_variable() {
         assert_eq!(
-            monomial("x"),
-            Ok((
-                "",
-                (Monomial::new(Number::new(1), Some("x"), Number::new(1),))
-            ))
+            expression::<false>("x"),
+            Ok(("", Polynomial::new("x", vec![0, 1]).into()))
         );
     }
     #[test]
-    fn parse_monomial_const() {
+    fn parse_term_const() {
         assert_eq!(
-            monomial("2"),
-            Ok(("", Monomial::new(Number::new(2), None, Number::new(1),)))
+            expression::<false>("2"),
+            Ok(("", Polynomial::new("", vec![2]).into()))
+        );
+    }
+
+    #[test]
+    fn parse_factored_expression() {
+        assert_eq!(
+            expression::<false>("(x-2)(x+3)"),
+            Ok(("", Polynomial::new("x", vec![-6, 1, 1]).into()))
+        );
+        assert_eq!(
+            expression::<false>("2(x+1)^2"),
+            Ok(("", Polynomial::new("x", vec![2, 4, 2]).into()))
+        );
+        assert_eq!(
+            expression::<false>("x(x-4)"),
+            Ok(("", Polynomial::new("x", vec![0, -4, 1]).into()))
+        );
+        assert_eq!(
+            expression::<false>("3 * x * (2 - x)"),
+            Ok(("", Polynomial::new("x", vec![0, 6, -3]).into()))
+        );
+        assert_eq!(
+            expression::<false>("-(x + 1) - (1 - x)"),
+            Ok(("", Polynomial::new("x", vec![-2, 0]).into()))
         );
     }
 
@@ -137,10 +551,13 @@ mod tests {
     fn parse_quadratic_must_err() {
         assert_eq!(
             quadratic("x^2+8y+15"),
-            Err(nom::Err::Error(Error::InvalidCharacter {
-                expected: "x".to_string(),
-                found: "y".to_string()
-            }))
+            Err(nom::Err::Failure(ErrorAt::new(
+                ErrorKind::InvalidCharacter {
+                    expected: "x".to_string(),
+                    found: "y".to_string()
+                },
+                "+8y"
+            )))
         );
     }
 
@@ -166,11 +583,354 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_factored_quadratic_inequality() {
+        assert_eq!(
+            quadratic_inequality("(x-2)(x+3)>0"),
+            Ok((
+                "",
+                QuadraticInequality::new(Quadratic::new("x".to_string(), 1, 1, -6), Sign::Gt)
+            ))
+        );
+        assert_eq!(
+            quadratic_inequality("2(x+1)^2<=8"),
+            Ok((
+                "",
+                QuadraticInequality::new(Quadratic::new("x".to_string(), 2, 4, -6), Sign::Lte)
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_expression_with_fractions() {
+        assert_eq!(
+            expression::<false>("1/2x^2 + 0.75x"),
+            Ok((
+                "",
+                Polynomial::new("x", vec![0, 3, 2])
+                    .mul(Polynomial::constant(Number::new(1, 4)))
+                    .unwrap()
+                    .into()
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_quadratic_inequality_with_whitespace() {
+        assert_eq!(
+            quadratic_inequality("x^2 + 3x - 10 > 0"),
+            Ok((
+                "",
+                QuadraticInequality::new(Quadratic::new("x".to_string(), 1, 3, -10), Sign::Gt)
+            ))
+        );
+        assert_eq!(
+            quadratic_inequality(" x ^ 2\t+3x-\u{a0}10>= 0 "),
+            Ok((
+                "",
+                QuadraticInequality::new(Quadratic::new("x".to_string(), 1, 3, -10), Sign::Gte)
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_rejects_trailing_input() {
+        assert_eq!(
+            parse("x^2>0>1"),
+            Err(Error::new(
+                ErrorKind::TrailingInput {
+                    found: ">1".to_string()
+                },
+                Span::new(5, 7)
+            ))
+        );
+        // a number is never multiplied by juxtaposition
+        assert_eq!(
+            parse("x^2 > 1 2"),
+            Err(Error::new(
+                ErrorKind::TrailingInput {
+                    found: "2".to_string()
+                },
+                Span::new(8, 9)
+            ))
+        );
+        // nor is anything after a space, unless it is in parentheses
+        for (input, span) in [
+            ("x^2-1>0 x", Span::new(8, 9)),
+            ("x^2 > 1 x", Span::new(8, 9)),
+        ] {
+            assert_eq!(
+                parse(input),
+                Err(Error::new(
+                    ErrorKind::TrailingInput {
+                        found: "x".to_string()
+                    },
+                    span
+                ))
+            );
+        }
+        assert_eq!(parse("2 (x + 1) > 0"), parse("2(x+1) > 0"));
+        assert_eq!(
+            parse("x^2-1>0 )"),
+            Err(Error::new(
+                ErrorKind::TrailingInput {
+                    found: ")".to_string()
+                },
+                Span::new(8, 9)
+            ))
+        );
+        assert_eq!(
+            parse("x^2-1>0abc"),
+            Err(Error::new(
+                ErrorKind::InvalidCharacter {
+                    expected: "x".to_string(),
+                    found: "abc".to_string()
+                },
+                Span::new(6, 10)
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_error_points_at_problem() {
+        let syntax = |expected, after: Option<&str>| ErrorKind::Syntax {
+            expected: Some(expected),
+            after: after.map(str::to_string),
+        };
+        assert_eq!(
+            parse("x^2+>0"),
+            Err(Error::new(
+                syntax(Expected::Operand, Some("+")),
+                Span::new(4, 5)
+            ))
+        );
+        assert_eq!(
+            parse("x^2 + 3x"),
+            Err(Error::new(
+                syntax(Expected::InequalitySign, None),
+                Span::new(8, 8)
+            ))
+        );
+        assert_eq!(
+            parse("x^2 = 0"),
+            Err(Error::new(
+                ErrorKind::InvalidIneqSign("=".to_string()),
+                Span::new(4, 5)
+            ))
+        );
+        assert_eq!(
+            parse("x^2 =< 0"),
+            Err(Error::new(
+                ErrorKind::InvalidIneqSign("=<".to_string()),
+                Span::new(4, 6)
+            ))
+        );
+        assert_eq!(
+            parse_chained("0 < x <> 1"),
+            Err(Error::new(
+                ErrorKind::InvalidIneqSign("<>".to_string()),
+                Span::new(6, 8)
+            ))
+        );
+        assert_eq!(
+            parse("x^ > 0"),
+            Err(Error::new(
+                syntax(Expected::Exponent, Some("^")),
+                Span::new(3, 4)
+            ))
+        );
+        assert_eq!(
+            parse("(x + 1 > 0"),
+            Err(Error::new(
+                syntax(Expected::ClosingParenthesis, None),
+                Span::new(7, 8)
+            ))
+        );
+        assert_eq!(
+            parse("x^2 >= *3"),
+            Err(Error::new(
+                syntax(Expected::Operand, Some(">=")),
+                Span::new(7, 8)
+            ))
+        );
+        assert_eq!(
+            parse("(x + 1)2 > 0"),
+            Err(Error::new(
+                syntax(Expected::InequalitySign, None),
+                Span::new(7, 8)
+            ))
+        );
+        assert_eq!(
+            parse("x^3 + x > 0"),
+            Err(Error::new(ErrorKind::InvalidQuadratic, Span::new(0, 7)))
+        );
+        assert_eq!(
+            parse("x^2 > 1/0 + x"),
+            Err(Error::new(ErrorKind::DivisionByZero, Span::new(6, 9)))
+        );
+    }
+
+    #[test]
+    fn parse_maps_every_outcome() {
+        let input = "x";
+        let fail = |error| finish::<()>(input, Err(error));
+        assert_eq!(
+            fail(nom::Err::Incomplete(nom::Needed::Unknown)),
+            Err(Error::new(ErrorKind::UnexpectedEnd, Span::new(1, 1)))
+        );
+        assert_eq!(
+            fail(nom::Err::Error(ErrorAt::new(ErrorKind::Overflow, input))),
+            Err(Error::new(ErrorKind::Overflow, Span::new(0, 1)))
+        );
+        assert_eq!(
+            fail(nom::Err::Failure(ErrorAt::new(ErrorKind::Overflow, input))),
+            Err(Error::new(ErrorKind::Overflow, Span::new(0, 1)))
+        );
+    }
+
+    #[test]
+    fn parse_rejects_deep_nesting() {
+        let nested = format!("{}x{}>0", "(".repeat(32), ")".repeat(32));
+        assert!(parse(&nested).is_ok());
+        let nested = format!("{}x{}>0", "(".repeat(33), ")".repeat(33));
+        assert_eq!(
+            parse(&nested),
+            Err(Error::new(ErrorKind::TooDeeplyNested, Span::new(32, 33)))
+        );
+    }
+
+    #[test]
+    fn parse_polynomial_inequality() {
+        assert_eq!(
+            parse_polynomial("x(x-2)(x+2) > 0"),
+            Ok(PolynomialInequality::new(
+                Polynomial::new("x", vec![0, -4, 0, 1]),
+                Sign::Gt
+            ))
+        );
+        assert_eq!(
+            parse_polynomial("x^3 > y"),
+            Err(Error::new(
+                ErrorKind::InvalidCharacter {
+                    expected: "x".to_string(),
+                    found: "y".to_string()
+                },
+                Span::new(6, 7)
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_rational_inequality() {
+        let x = |coefficients| Polynomial::new("x", coefficients);
+        assert_eq!(
+            parse_rational("x/(x-3) < 2"),
+            Ok(RationalInequality::new(
+                RationalFunction::new(x(vec![6, -1]), x(vec![-3, 1])).unwrap(),
+                Sign::Lt
+            ))
+        );
+        assert_eq!(
+            parse_rational("x/2 + 1/2x >= 1"),
+            Ok(RationalInequality::new(x(vec![-1, 1]).into(), Sign::Gte))
+        );
+        assert_eq!(
+            parse_rational("1/(x-x) > 0"),
+            Err(Error::new(ErrorKind::DivisionByZero, Span::new(1, 7)))
+        );
+        assert_eq!(
+            parse_polynomial("1/x > 0"),
+            Err(Error::new(ErrorKind::InvalidPolynomial, Span::new(0, 3)))
+        );
+    }
+
+    #[test]
+    fn parse_absolute_inequality() {
+        let x = |coefficients| Expression::from(Polynomial::new("x", coefficients));
+        let constant = |c| Expression::from(Polynomial::constant(Number::from(c)));
+        assert_eq!(
+            parse_absolute("||x|-1| < 1"),
+            Ok(AbsoluteInequality::new(
+                Expression::Add(
+                    Box::new(Expression::Abs(Box::new(Expression::Add(
+                        Box::new(Expression::Abs(Box::new(x(vec![0, 1])))),
+                        Box::new(constant(-1))
+                    )))),
+                    Box::new(constant(-1))
+                ),
+                Sign::Lt
+            ))
+        );
+        assert_eq!(
+            parse_absolute("|x-3 < 2"),
+            Err(Error::new(
+                ErrorKind::Syntax {
+                    expected: Some(Expected::ClosingBar),
+                    after: None
+                },
+                Span::new(5, 6)
+            ))
+        );
+        assert_eq!(
+            parse_rational("|x| > 0"),
+            Err(Error::new(ErrorKind::AbsoluteValue, Span::new(0, 3)))
+        );
+        let bars = format!("{}x{} > 0", "|".repeat(8), "|".repeat(8));
+        assert!(parse_absolute(&bars).is_ok());
+        let bars = format!("{}x{} > 0", "|".repeat(9), "|".repeat(9));
+        assert_eq!(
+            parse_absolute(&bars),
+            Err(Error::new(
+                ErrorKind::TooManyAbsoluteValues,
+                Span::new(17, 18)
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_chained_inequality() {
+        let x = |coefficients| Expression::from(Polynomial::new("x", coefficients));
+        let constant = |c| Expression::from(Polynomial::constant(Number::from(c)));
+        assert_eq!(
+            parse_chained("-3 < x^2-4x < 5").map(|chain| chain.sides().to_vec()),
+            Ok(vec![constant(-3), x(vec![0, -4, 1]), constant(5)])
+        );
+        assert_eq!(
+            parse_chained("x < 1 < y"),
+            Err(Error::new(
+                ErrorKind::InvalidCharacter {
+                    expected: "x".to_string(),
+                    found: "y".to_string()
+                },
+                Span::new(8, 9)
+            ))
+        );
+        assert_eq!(
+            parse_chained("0 < x <"),
+            Err(Error::new(
+                ErrorKind::Syntax {
+                    expected: Some(Expected::Operand),
+                    after: Some("<".to_string())
+                },
+                Span::new(7, 7)
+            ))
+        );
+        assert_eq!(
+            parse("-3 < x^2 < 5"),
+            Err(Error::new(
+                ErrorKind::TrailingInput {
+                    found: "< 5".to_string()
+                },
+                Span::new(9, 12)
+            ))
+        );
+    }
+
     #[test]
     fn parse_and_get_solution_of_quadratic_inequality() {
         assert_eq!(
-            parse("x^2+3x-10≥0").unwrap().get_solution(),
-            "x ≤ -5 OR x ≥ 2".to_string(),
+            parse("x^2+3x-10≥0").unwrap().solve().unwrap().to_string(),
+            "x ≤ -5 OR x ≥ 2",
         );
     }
 }
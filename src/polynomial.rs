@@ -0,0 +1,556 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::{
+    error::ErrorKind,
+    explanation::{Explanation, Step},
+    real::Real,
+    solution::{Bound, Interval, SolutionSet, Symbols},
+    types::{Number, Polynomial, Pretty, Quadratic, QuadraticInequality, Sign},
+};
+
+/// how far to search for divisors of a leading coefficient. above the square of this,
+/// a rational root with a large denominator may be missed and only approximated.
+const MAX_DIVISOR: i64 = 100_000;
+/// bisection steps per root, more than enough to exhaust the precision of an `f64`.
+const BISECTIONS: usize = 200;
+/// how close, relative to its size, an exact root must be to a numerical one to be
+/// taken for it.
+const TOLERANCE: f64 = 1e-9;
+
+/// a real root of a polynomial, and how many times the polynomial has it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Root {
+    value: Real,
+    multiplicity: u32,
+}
+impl Root {
+    pub fn value(&self) -> Real {
+        self.value
+    }
+    /// 1 for a simple root, 2 for a double root and so on.
+    pub fn multiplicity(&self) -> u32 {
+        self.multiplicity
+    }
+}
+
+/// the positive divisors of `n`, smaller first.
+fn divisors(n: i64) -> Vec<i64> {
+    let n = n.abs();
+    let mut small = Vec::new();
+    let mut large = Vec::new();
+    for d in (1..=MAX_DIVISOR).take_while(|d| d * d <= n) {
+        if n % d == 0 {
+            small.push(d);
+            if d * d != n {
+                large.push(n / d);
+            }
+        }
+    }
+    small.extend(large.into_iter().rev());
+    small
+}
+
+/// `x` rounded to an integer, if it fits.
+fn round(x: f64) -> Option<i64> {
+    let x = x.round();
+    // also false for NaN
+    if x.abs() < i64::MAX as f64 {
+        Some(x as i64)
+    } else {
+        None
+    }
+}
+
+/// the distinct real roots of the polynomial with `coefficients`, smaller first. between
+/// two neighbouring roots of the derivative the polynomial is monotonic, so it crosses
+/// zero at most once there and bisection finds where.
+fn approximate_roots(coefficients: &[f64]) -> Vec<f64> {
+    let degree = match coefficients.iter().rposition(|c| *c != 0.0) {
+        Some(degree) if degree > 0 => degree,
+        _ => return Vec::new(),
+    };
+    let coefficients = &coefficients[..=degree];
+    if degree == 1 {
+        return vec![-coefficients[0] / coefficients[1]];
+    }
+    let value = |x: f64| {
+        coefficients
+            .iter()
+            .rev()
+            .fold(0.0, |value, c| value * x + c)
+    };
+    // every root lies strictly within the Cauchy bound
+    let leading = coefficients[degree];
+    let bound = 1.0
+        + coefficients[..degree]
+            .iter()
+            .map(|c| (c / leading).abs())
+            .fold(0.0, f64::max);
+    let derivative: Vec<f64> = coefficients
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(n, c)| c * n as f64)
+        .collect();
+    let mut points = vec![-bound];
+    points.extend(
+        approximate_roots(&derivative)
+            .into_iter()
+            .filter(|x| x.abs() < bound),
+    );
+    points.push(bound);
+    let mut roots: Vec<f64> = Vec::new();
+    for pair in points.windows(2) {
+        let (mut low, mut high) = (pair[0], pair[1]);
+        let (at_low, at_high) = (value(low), value(high));
+        if at_low == 0.0 {
+            if roots.last() != Some(&low) {
+                roots.push(low);
+            }
+            continue;
+        }
+        if at_high == 0.0 || (at_low < 0.0) == (at_high < 0.0) {
+            // a root at `high` is found as the `low` of the next pair
+            continue;
+        }
+        let rising = at_high > 0.0;
+        for _ in 0..BISECTIONS {
+            let middle = low + (high - low) / 2.0;
+            if middle <= low || middle >= high {
+                break;
+            }
+            let at_middle = value(middle);
+            if at_middle == 0.0 {
+                low = middle;
+                high = middle;
+            } else if (at_middle > 0.0) == rising {
+                high = middle;
+            } else {
+                low = middle;
+            }
+        }
+        roots.push(low + (high - low) / 2.0);
+    }
+    roots.retain(|x| x.is_finite());
+    roots
+}
+
+/// the factors of `polynomial` that have no repeated roots, by Yun's algorithm:
+/// `factors[i]` has exactly the roots of multiplicity `i + 1`.
+fn square_free_factors(polynomial: &Polynomial) -> Result<Vec<Polynomial>, ErrorKind> {
+    let derivative = polynomial.derivative()?;
+    let repeated = polynomial.gcd(&derivative)?;
+    let (mut rest, _) = polynomial.div_rem(&repeated)?;
+    let (quotient, _) = derivative.div_rem(&repeated)?;
+    let mut difference = quotient.add(rest.derivative()?.negate()?)?;
+    let mut factors = Vec::new();
+    while rest.degree() > 0 {
+        let factor = rest.gcd(&difference)?;
+        let (next, _) = rest.div_rem(&factor)?;
+        let (quotient, _) = difference.div_rem(&factor)?;
+        rest = next;
+        difference = quotient.add(rest.derivative()?.negate()?)?;
+        factors.push(factor);
+    }
+    Ok(factors)
+}
+
+/// a rational root of `polynomial` near `x`. its denominator divides `leading`, by
+/// the rational root theorem.
+fn rational_root_near(polynomial: &Polynomial, leading: i64, x: f64) -> Option<Number> {
+    divisors(leading).into_iter().find_map(|q| {
+        let root = Number::from_fraction(round(x * q as f64)?, q).ok()?;
+        match polynomial.evaluate(root) {
+            Ok(value) if value.is_zero() => Some(root),
+            _ => None,
+        }
+    })
+}
+
+/// whether `exact` is the root that `approximation` was found for.
+fn is_near(exact: &Real, approximation: f64) -> bool {
+    (exact.to_f64() - approximation).abs() <= TOLERANCE * approximation.abs().max(1.0)
+}
+
+/// a factor `ax^2 + bx + c` of `polynomial` with integer coefficients whose roots are
+/// `x` and `y`, where `x < y`, together with its exact roots. a factor that divides
+/// but has other roots is not taken.
+fn quadratic_factor_near(
+    polynomial: &Polynomial,
+    leading: i64,
+    x: f64,
+    y: f64,
+) -> Option<(Polynomial, Vec<Real>)> {
+    divisors(leading).into_iter().find_map(|a| {
+        let b = round(-(x + y) * a as f64)?;
+        let c = round(x * y * a as f64)?;
+        let factor = Polynomial::from_coefficients(
+            polynomial.character(),
+            vec![Number::from(c), Number::from(b), Number::from(a)],
+        );
+        let (_, remainder) = polynomial.div_rem(&factor).ok()?;
+        if !remainder.is_zero() {
+            return None;
+        }
+        let roots: Vec<Real> = Quadratic::from_polynomial(factor.clone())
+            .ok()?
+            .roots()
+            .ok()?
+            .into_iter()
+            .map(Real::from)
+            .collect();
+        match roots.as_slice() {
+            [low, high] if is_near(low, x) && is_near(high, y) => Some((factor, roots)),
+            _ => None,
+        }
+    })
+}
+
+/// the distinct real roots of `polynomial`, which has no repeated roots. rational
+/// roots and roots of rational quadratic factors are found exactly by checking the
+/// numerical roots against the candidates nearby.
+fn square_free_roots(polynomial: &Polynomial) -> Result<Vec<Real>, ErrorKind> {
+    let mut rest = polynomial.primitive()?;
+    let leading = rest.leading().numerator();
+    let approximations: Vec<f64> = (0..=rest.degree())
+        .map(|n| rest.coefficient(n).to_f64())
+        .collect();
+    let mut roots = Vec::new();
+    let mut irrational = Vec::new();
+    for x in approximate_roots(&approximations) {
+        match rational_root_near(&rest, leading, x) {
+            Some(root) => {
+                let linear = Polynomial::from_coefficients(
+                    rest.character(),
+                    vec![root.checked_neg()?, Number::from(1)],
+                );
+                rest = rest.div_rem(&linear)?.0;
+                roots.push(Real::from(root.to_surd()));
+            }
+            None => irrational.push(x),
+        }
+    }
+    let mut i = 0;
+    while rest.degree() > 2 && i < irrational.len() {
+        let factor = (i + 1..irrational.len()).find_map(|j| {
+            quadratic_factor_near(&rest, leading, irrational[i], irrational[j])
+                .map(|factor| (j, factor))
+        });
+        match factor {
+            Some((j, (factor, exact))) => {
+                rest = rest.div_rem(&factor)?.0;
+                roots.extend(exact);
+                irrational.remove(j);
+                irrational.remove(i);
+            }
+            None => i += 1,
+        }
+    }
+    if rest.degree() <= 2 {
+        let exact = Quadratic::from_polynomial(rest)?.roots()?;
+        roots.extend(exact.into_iter().map(Real::from));
+    } else {
+        roots.extend(irrational.into_iter().map(Real::Approx));
+    }
+    Ok(roots)
+}
+
+impl Polynomial {
+    /// the distinct real roots, smaller first, each with its multiplicity. a root is
+    /// exact if it is rational or a root of a rational quadratic factor, and an
+    /// approximation otherwise. a constant has none.
+    pub fn real_roots(&self) -> Result<Vec<Root>, ErrorKind> {
+        let one = Polynomial::constant(Number::from(1));
+        Ok(self
+            .real_roots_shared_with(&one)?
+            .into_iter()
+            .map(|(root, _)| root)
+            .collect())
+    }
+    /// like [`Polynomial::real_roots`], with whether each root is also a root of `other`.
+    pub(crate) fn real_roots_shared_with(
+        &self,
+        other: &Polynomial,
+    ) -> Result<Vec<(Root, bool)>, ErrorKind> {
+        if self.degree() == 0 {
+            return Ok(Vec::new());
+        }
+        let mut roots = Vec::new();
+        for (i, factor) in square_free_factors(self)?.iter().enumerate() {
+            let multiplicity = i as u32 + 1;
+            // an exact split, so a shared root is not approximated twice and missed
+            let shared = factor.gcd(other)?;
+            let (own, _) = factor.div_rem(&shared)?;
+            for (part, is_shared) in [(own, false), (shared, true)] {
+                roots.extend(square_free_roots(&part)?.into_iter().map(|value| {
+                    let root = Root {
+                        value,
+                        multiplicity,
+                    };
+                    (root, is_shared)
+                }));
+            }
+        }
+        roots.sort_by_key(|(root, _)| root.value);
+        Ok(roots)
+    }
+}
+
+/// the sign left of the first of `roots`, between each pair of neighbours and right of
+/// the last. right of every root it is `rightmost`, and moving left it changes at each
+/// root of odd multiplicity.
+pub(crate) fn sign_chart(roots: &[Root], rightmost: Ordering) -> Vec<Ordering> {
+    let mut signs = vec![rightmost];
+    for root in roots.iter().rev() {
+        let right = signs[signs.len() - 1];
+        signs.push(match root.multiplicity % 2 {
+            1 => right.reverse(),
+            _ => right,
+        });
+    }
+    signs.reverse();
+    signs
+}
+
+/// the values where `sign` holds according to the sign chart `signs` of `roots`: the
+/// gaps with the right sign, and the roots themselves unless they are `excluded`.
+pub(crate) fn read_sign_chart(
+    character: &str,
+    roots: &[Root],
+    excluded: &[bool],
+    signs: &[Ordering],
+    sign: &Sign,
+) -> SolutionSet {
+    let mut intervals = Vec::new();
+    for (i, gap) in signs.iter().enumerate() {
+        if sign.holds(*gap) {
+            let lower = match i {
+                0 => Bound::Unbounded,
+                i => Bound::Open(roots[i - 1].value),
+            };
+            let upper = roots
+                .get(i)
+                .map_or(Bound::Unbounded, |r| Bound::Open(r.value));
+            intervals.push(Interval::new(lower, upper));
+        }
+    }
+    if sign.holds(Ordering::Equal) {
+        for (root, excluded) in roots.iter().zip(excluded) {
+            if !excluded {
+                intervals.push(Interval::point(root.value));
+            }
+        }
+    }
+    SolutionSet::from_intervals(character, intervals)
+}
+
+/// `polynomial sign 0`, with every term moved to the left-hand side.
+#[derive(Debug, PartialEq)]
+pub struct PolynomialInequality {
+    polynomial: Polynomial,
+    sign: Sign,
+}
+impl PolynomialInequality {
+    /// `polynomial sign 0`.
+    pub fn new(polynomial: Polynomial, sign: Sign) -> Self {
+        Self { polynomial, sign }
+    }
+    /// `left sign right`, with `right` moved over to the left-hand side. fails if the
+    /// sides are in different variables, or if a coefficient overflows.
+    pub fn from_sides(left: Polynomial, sign: Sign, right: Polynomial) -> Result<Self, ErrorKind> {
+        Self::from_expr((left, sign, right))
+    }
+    pub(crate) fn from_expr(input: (Polynomial, Sign, Polynomial)) -> Result<Self, ErrorKind> {
+        let (left, sign, right) = input;
+        Ok(Self {
+            polynomial: left.add(right.negate()?)?,
+            sign,
+        })
+    }
+    /// everything moved to the left-hand side.
+    pub fn polynomial(&self) -> &Polynomial {
+        &self.polynomial
+    }
+    pub fn sign(&self) -> &Sign {
+        &self.sign
+    }
+    /// the values of the variable for which the inequality holds.
+    pub fn solve(&self) -> Result<SolutionSet, ErrorKind> {
+        self.solve_with(&mut Vec::new())
+    }
+    /// the solution worked out step by step, starting from the normalized form.
+    pub fn explain(&self) -> Result<Explanation, ErrorKind> {
+        self.explain_with(Vec::new())
+    }
+    /// the explanation, following the `steps` that led to the normalized form.
+    pub(crate) fn explain_with(&self, mut steps: Vec<Step>) -> Result<Explanation, ErrorKind> {
+        steps.push(Step::CollectTerms {
+            polynomial: self.polynomial.clone(),
+            sign: self.sign.clone(),
+        });
+        let solution = self.solve_with(&mut steps)?;
+        steps.push(Step::Answer(solution));
+        Ok(Explanation::new(steps))
+    }
+    /// the solution, with the steps taken to reach it pushed onto `steps`. up to
+    /// degree 2 the roots are always exact, and the quadratic method explains better.
+    pub(crate) fn solve_with(&self, steps: &mut Vec<Step>) -> Result<SolutionSet, ErrorKind> {
+        match Quadratic::from_polynomial(self.polynomial.clone()) {
+            Ok(quadratic) => {
+                QuadraticInequality::new(quadratic, self.sign.clone()).solve_with(steps)
+            }
+            Err(_) => self.get_sign_chart_solution(steps),
+        }
+    }
+    fn get_sign_chart_solution(&self, steps: &mut Vec<Step>) -> Result<SolutionSet, ErrorKind> {
+        let character = self.polynomial.character();
+        let roots = self.polynomial.real_roots()?;
+        steps.push(Step::PolynomialRoots {
+            character: character.to_string(),
+            roots: roots.clone(),
+        });
+        let signs = sign_chart(&roots, self.polynomial.leading().signum());
+        steps.push(Step::SignChart {
+            roots: roots.iter().map(Root::value).collect(),
+            signs: signs.clone(),
+        });
+        steps.push(Step::ChooseSigns {
+            sign: self.sign.clone(),
+            inclusive: self.sign.is_inclusive(),
+        });
+        let excluded = vec![false; roots.len()];
+        Ok(read_sign_chart(
+            character, &roots, &excluded, &signs, &self.sign,
+        ))
+    }
+}
+
+impl PolynomialInequality {
+    /// the normalized inequality written with `symbols`: `x³ - 4x > 0`, or
+    /// `x^3 - 4x > 0` in ASCII.
+    pub fn pretty(&self, symbols: Symbols) -> Pretty<'_, Self> {
+        Pretty {
+            value: self,
+            symbols,
+        }
+    }
+}
+
+/// the normalized form `x^3 - 4x > 0`.
+impl fmt::Display for PolynomialInequality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} 0", self.polynomial, self.sign)
+    }
+}
+impl fmt::Display for Pretty<'_, PolynomialInequality> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inequality = self.value;
+        write!(f, "{} ", inequality.polynomial.pretty(self.symbols))?;
+        match self.symbols {
+            Symbols::Unicode => write!(f, "{}", inequality.sign)?,
+            Symbols::Ascii => write!(f, "{}", inequality.sign.ascii())?,
+        }
+        write!(f, " 0")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surd::Surd;
+
+    fn polynomial(coefficients: Vec<i64>) -> Polynomial {
+        Polynomial::new("x", coefficients)
+    }
+    fn exact(value: Surd, multiplicity: u32) -> Root {
+        Root {
+            value: value.into(),
+            multiplicity,
+        }
+    }
+
+    #[test]
+    fn find_rational_roots_with_multiplicity() {
+        // (x - 1)^2 (x + 2) (x - 3)
+        assert_eq!(
+            polynomial(vec![-6, 11, -3, -3, 1]).real_roots(),
+            Ok(vec![
+                exact(Surd::integer(-2), 1),
+                exact(Surd::integer(1), 2),
+                exact(Surd::integer(3), 1),
+            ])
+        );
+        // (2x - 1)^3 x
+        assert_eq!(
+            polynomial(vec![0, -1, 6, -12, 8]).real_roots(),
+            Ok(vec![
+                exact(Surd::integer(0), 1),
+                exact(Surd::rational(1, 2), 3),
+            ])
+        );
+    }
+
+    #[test]
+    fn find_roots_of_quadratic_factors() {
+        // (x^2 - 2)(x^2 - 3)
+        assert_eq!(
+            polynomial(vec![6, 0, -5, 0, 1]).real_roots(),
+            Ok(vec![
+                exact(Surd::new(0, -1, 3, 1), 1),
+                exact(Surd::new(0, -1, 2, 1), 1),
+                exact(Surd::new(0, 1, 2, 1), 1),
+                exact(Surd::new(0, 1, 3, 1), 1),
+            ])
+        );
+        // (x - 1)(x^2 + 1)
+        assert_eq!(
+            polynomial(vec![-1, 1, -1, 1]).real_roots(),
+            Ok(vec![exact(Surd::integer(1), 1)])
+        );
+    }
+
+    #[test]
+    fn approximate_irrational_roots() {
+        let roots = polynomial(vec![-2, 0, 0, 1]).real_roots().unwrap();
+        assert_eq!(roots.len(), 1);
+        assert!(!roots[0].value().is_exact());
+        assert!((roots[0].value().to_f64() - 2f64.cbrt()).abs() < 1e-12);
+        // x^5 - x - 1 has a single real root near 1.1673
+        let roots = polynomial(vec![-1, -1, 0, 0, 0, 1]).real_roots().unwrap();
+        assert_eq!(roots.len(), 1);
+        assert!((roots[0].value().to_f64() - 1.167_303_978_3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn solve_with_sign_chart() {
+        let solve = |coefficients, sign| {
+            PolynomialInequality::new(polynomial(coefficients), sign)
+                .solve()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(solve(vec![0, -4, 0, 1], Sign::Gt), "-2 < x < 0 OR x > 2");
+        assert_eq!(solve(vec![-6, 11, -3, -3, 1], Sign::Lte), "-2 ≤ x ≤ 3");
+        assert_eq!(
+            solve(vec![-6, 11, -3, -3, 1], Sign::Lt),
+            "-2 < x < 1 OR 1 < x < 3"
+        );
+        assert_eq!(solve(vec![0, 0, 0, 0, 1], Sign::Lte), "x = 0");
+        assert_eq!(solve(vec![1, 0, 0, 0, 1], Sign::Lt), "no solution");
+        assert_eq!(solve(vec![-2, 0, 0, 1], Sign::Gte), "x ≥ ≈1.259921");
+    }
+
+    #[test]
+    fn display_polynomial_inequality() {
+        let inequality = PolynomialInequality::new(polynomial(vec![0, -4, 0, 1]), Sign::Gte);
+        assert_eq!(inequality.to_string(), "x^3 - 4x ≥ 0");
+        assert_eq!(
+            inequality.pretty(Symbols::Unicode).to_string(),
+            "x³ - 4x ≥ 0"
+        );
+        assert_eq!(
+            inequality.pretty(Symbols::Ascii).to_string(),
+            "x^3 - 4x >= 0"
+        );
+    }
+}
@@ -0,0 +1,488 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::{
+    error::ErrorKind,
+    explanation::{Explanation, Step},
+    polynomial::{read_sign_chart, sign_chart, PolynomialInequality, Root},
+    real::Real,
+    solution::{SolutionSet, Symbols},
+    types::{Number, Polynomial, Pretty, Sign},
+};
+
+/// a polynomial divided by another, such as `(x - 1)/(x + 2)`. common factors are
+/// never cancelled: where the denominator is 0 the function is undefined, even if the
+/// numerator is 0 there too.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RationalFunction {
+    numerator: Polynomial,
+    denominator: Polynomial,
+}
+impl RationalFunction {
+    /// `numerator/denominator`. a constant denominator is divided into the numerator,
+    /// so a polynomial always has the denominator 1. fails with
+    /// [`ErrorKind::DivisionByZero`] if the denominator is zero.
+    pub fn new(numerator: Polynomial, denominator: Polynomial) -> Result<Self, ErrorKind> {
+        if denominator.is_zero() {
+            return Err(ErrorKind::DivisionByZero);
+        }
+        if denominator.degree() > 0 {
+            return Ok(Self {
+                numerator,
+                denominator,
+            });
+        }
+        if denominator.coefficient(0) == Number::from(1) {
+            return Ok(Self::from(numerator));
+        }
+        let scale = Polynomial::from_coefficients(
+            denominator.character(),
+            vec![Number::from(1).checked_div(denominator.coefficient(0))?],
+        );
+        Ok(Self {
+            numerator: numerator.mul(scale)?,
+            denominator: Polynomial::constant(Number::from(1)),
+        })
+    }
+    pub fn numerator(&self) -> &Polynomial {
+        &self.numerator
+    }
+    /// `1` for a polynomial.
+    pub fn denominator(&self) -> &Polynomial {
+        &self.denominator
+    }
+    /// the variable, or `""` for a constant.
+    pub fn character(&self) -> &str {
+        match self.numerator.character() {
+            "" => self.denominator.character(),
+            character => character,
+        }
+    }
+    /// whether the denominator is constant.
+    pub fn is_polynomial(&self) -> bool {
+        self.denominator.degree() == 0
+    }
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+    /// whether there is no variable left.
+    pub fn is_constant(&self) -> bool {
+        self.is_polynomial() && self.numerator.degree() == 0
+    }
+    /// the numerator, if the denominator is constant.
+    pub(crate) fn into_polynomial(self) -> Result<Polynomial, ErrorKind> {
+        if !self.is_polynomial() {
+            return Err(ErrorKind::InvalidPolynomial);
+        }
+        Ok(self.numerator)
+    }
+    pub(crate) fn negate(self) -> Result<Self, ErrorKind> {
+        Ok(Self {
+            numerator: self.numerator.negate()?,
+            denominator: self.denominator,
+        })
+    }
+    pub(crate) fn add(self, rhs: Self) -> Result<Self, ErrorKind> {
+        if self.denominator == rhs.denominator {
+            return Self::new(self.numerator.add(rhs.numerator)?, self.denominator);
+        }
+        let numerator = self
+            .numerator
+            .mul(rhs.denominator.clone())?
+            .add(rhs.numerator.mul(self.denominator.clone())?)?;
+        Self::new(numerator, self.denominator.mul(rhs.denominator)?)
+    }
+    pub(crate) fn mul(self, rhs: Self) -> Result<Self, ErrorKind> {
+        Self::new(
+            self.numerator.mul(rhs.numerator)?,
+            self.denominator.mul(rhs.denominator)?,
+        )
+    }
+    /// `1` divided by this. fails with [`ErrorKind::DivisionByZero`] if this is zero.
+    pub(crate) fn reciprocal(self) -> Result<Self, ErrorKind> {
+        Self::new(self.denominator, self.numerator)
+    }
+    pub(crate) fn pow(self, exponent: u32) -> Result<Self, ErrorKind> {
+        Self::new(
+            self.numerator.pow(exponent)?,
+            self.denominator.pow(exponent)?,
+        )
+    }
+    /// the distinct real roots of the numerator times the denominator, which has the
+    /// same sign wherever the function is defined, smaller first. each comes with
+    /// whether it is a root of the denominator, where the function is undefined.
+    pub(crate) fn roots(&self) -> Result<Vec<(Root, bool)>, ErrorKind> {
+        if self.numerator.is_zero() {
+            return Ok(self
+                .denominator
+                .real_roots()?
+                .into_iter()
+                .map(|root| (root, true))
+                .collect());
+        }
+        self.numerator
+            .clone()
+            .mul(self.denominator.clone())?
+            .real_roots_shared_with(&self.denominator)
+    }
+    /// the distinct real values where the function is 0, smaller first, each with its
+    /// multiplicity as a root of the numerator.
+    pub fn zeros(&self) -> Result<Vec<Root>, ErrorKind> {
+        Ok(self
+            .roots()?
+            .into_iter()
+            .filter(|(_, excluded)| !excluded)
+            .map(|(root, _)| root)
+            .collect())
+    }
+    /// the distinct real values where the denominator is 0 and the function is
+    /// undefined, smaller first.
+    pub fn poles(&self) -> Result<Vec<Real>, ErrorKind> {
+        Ok(self
+            .roots()?
+            .into_iter()
+            .filter(|(_, excluded)| *excluded)
+            .map(|(root, _)| root.value())
+            .collect())
+    }
+    /// write `numerator/denominator`, with parentheses around either unless it is a
+    /// single term that cannot be misread: `x^2/(x - 1)`, `1/(2x)`, `(1/2x)/x`.
+    fn fmt_with(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        fmt: impl Fn(&Polynomial, &mut fmt::Formatter<'_>) -> fmt::Result,
+    ) -> fmt::Result {
+        if self.is_polynomial() {
+            return fmt(&self.numerator, f);
+        }
+        for (i, polynomial) in [&self.numerator, &self.denominator].iter().enumerate() {
+            if i > 0 {
+                write!(f, "/")?;
+            }
+            let parenthesized = match polynomial.terms().collect::<Vec<_>>().as_slice() {
+                // in the numerator only a fraction would run into the `/`
+                [(coefficient, _)] if i == 0 => coefficient.denominator() != 1,
+                [(coefficient, _)] => *coefficient != Number::from(1),
+                [] => false,
+                _ => true,
+            };
+            if parenthesized {
+                write!(f, "(")?;
+                fmt(polynomial, f)?;
+                write!(f, ")")?;
+            } else {
+                fmt(polynomial, f)?;
+            }
+        }
+        Ok(())
+    }
+    /// the function written with `symbols`: `x²/(x - 1)` or plain `x^2/(x - 1)`.
+    pub fn pretty(&self, symbols: Symbols) -> Pretty<'_, Self> {
+        Pretty {
+            value: self,
+            symbols,
+        }
+    }
+}
+
+impl From<Polynomial> for RationalFunction {
+    fn from(polynomial: Polynomial) -> Self {
+        Self {
+            numerator: polynomial,
+            denominator: Polynomial::constant(Number::from(1)),
+        }
+    }
+}
+
+/// `(x - 1)/(x + 2)`, or just the numerator for a polynomial.
+impl fmt::Display for RationalFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, |polynomial, f| write!(f, "{}", polynomial))
+    }
+}
+impl fmt::Display for Pretty<'_, RationalFunction> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbols = self.symbols;
+        self.value.fmt_with(f, |polynomial, f| {
+            write!(f, "{}", polynomial.pretty(symbols))
+        })
+    }
+}
+
+/// `function sign 0`, with every term moved to the left-hand side and brought over a
+/// common denominator.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RationalInequality {
+    function: RationalFunction,
+    sign: Sign,
+}
+impl RationalInequality {
+    /// `function sign 0`.
+    pub fn new(function: RationalFunction, sign: Sign) -> Self {
+        Self { function, sign }
+    }
+    /// `left sign right`, with `right` moved over to the left-hand side. fails if the
+    /// sides are in different variables, or if a coefficient overflows.
+    pub fn from_sides(
+        left: RationalFunction,
+        sign: Sign,
+        right: RationalFunction,
+    ) -> Result<Self, ErrorKind> {
+        Self::from_expr((left, sign, right))
+    }
+    pub(crate) fn from_expr(
+        input: (RationalFunction, Sign, RationalFunction),
+    ) -> Result<Self, ErrorKind> {
+        let (left, sign, right) = input;
+        Ok(Self {
+            function: left.add(right.negate()?)?,
+            sign,
+        })
+    }
+    /// everything moved to the left-hand side.
+    pub fn function(&self) -> &RationalFunction {
+        &self.function
+    }
+    pub fn sign(&self) -> &Sign {
+        &self.sign
+    }
+    /// the values of the variable for which the inequality holds. the roots of the
+    /// denominator never belong to it, even for `≤` and `≥`.
+    pub fn solve(&self) -> Result<SolutionSet, ErrorKind> {
+        self.solve_with(&mut Vec::new())
+    }
+    /// the solution worked out step by step, starting from the normalized form.
+    pub fn explain(&self) -> Result<Explanation, ErrorKind> {
+        self.explain_with(Vec::new())
+    }
+    /// work out the solution of `left sign right`, recording every step.
+    pub(crate) fn explain_sides(
+        input: (RationalFunction, Sign, RationalFunction),
+    ) -> Result<Explanation, ErrorKind> {
+        let (left, sign, right) = input;
+        let mut steps = Vec::new();
+        if !right.is_zero() {
+            steps.push(Step::MoveToOneSide {
+                left: left.clone(),
+                sign: sign.clone(),
+                right: right.clone(),
+            });
+        }
+        Self::from_expr((left, sign, right))?.explain_with(steps)
+    }
+    pub(crate) fn explain_with(&self, mut steps: Vec<Step>) -> Result<Explanation, ErrorKind> {
+        if let Some(inequality) = self.polynomial_inequality() {
+            return inequality.explain_with(steps);
+        }
+        steps.push(Step::CommonDenominator {
+            function: self.function.clone(),
+            sign: self.sign.clone(),
+        });
+        let solution = self.solve_with(&mut steps)?;
+        steps.push(Step::Answer(solution));
+        Ok(Explanation::new(steps))
+    }
+    /// the same inequality as a polynomial one, if the denominator is constant.
+    fn polynomial_inequality(&self) -> Option<PolynomialInequality> {
+        let polynomial = self.function.clone().into_polynomial().ok()?;
+        Some(PolynomialInequality::new(polynomial, self.sign.clone()))
+    }
+    /// the solution, with the steps taken to reach it pushed onto `steps`.
+    pub(crate) fn solve_with(&self, steps: &mut Vec<Step>) -> Result<SolutionSet, ErrorKind> {
+        match self.polynomial_inequality() {
+            Some(inequality) => inequality.solve_with(steps),
+            None => self.get_sign_chart_solution(steps),
+        }
+    }
+    /// the numerator divided by the denominator has the same sign as their product,
+    /// except at the roots of the denominator, which are left out.
+    fn get_sign_chart_solution(&self, steps: &mut Vec<Step>) -> Result<SolutionSet, ErrorKind> {
+        let character = self.function.character();
+        let (roots, excluded): (Vec<Root>, Vec<bool>) = self.function.roots()?.into_iter().unzip();
+        let poles: Vec<Real> = roots
+            .iter()
+            .zip(&excluded)
+            .filter(|(_, excluded)| **excluded)
+            .map(|(root, _)| root.value())
+            .collect();
+        steps.push(Step::ExcludePoles {
+            character: character.to_string(),
+            poles: poles.clone(),
+        });
+        if self.function.is_zero() {
+            // 0 wherever it is defined
+            let holds = self.sign.holds(Ordering::Equal);
+            steps.push(Step::Constant {
+                c: Number::from(0),
+                sign: self.sign.clone(),
+                holds,
+            });
+            if !holds {
+                return Ok(SolutionSet::empty(character));
+            }
+            let signs = vec![Ordering::Equal; roots.len() + 1];
+            return Ok(read_sign_chart(
+                character, &roots, &excluded, &signs, &self.sign,
+            ));
+        }
+        steps.push(Step::NumeratorRoots {
+            character: character.to_string(),
+            roots: roots
+                .iter()
+                .zip(&excluded)
+                .filter(|(_, excluded)| !**excluded)
+                .map(|(root, _)| *root)
+                .collect(),
+        });
+        let product = self
+            .function
+            .numerator()
+            .leading()
+            .checked_mul(self.function.denominator().leading())?;
+        let signs = sign_chart(&roots, product.signum());
+        steps.push(Step::SignChart {
+            roots: roots.iter().map(Root::value).collect(),
+            signs: signs.clone(),
+        });
+        steps.push(Step::ChooseSigns {
+            sign: self.sign.clone(),
+            inclusive: self.sign.is_inclusive(),
+        });
+        Ok(read_sign_chart(
+            character, &roots, &excluded, &signs, &self.sign,
+        ))
+    }
+}
+
+impl RationalInequality {
+    /// the normalized inequality written with `symbols`: `(x - 1)/(x + 2) ≥ 0`, or
+    /// `(x - 1)/(x + 2) >= 0` in ASCII.
+    pub fn pretty(&self, symbols: Symbols) -> Pretty<'_, Self> {
+        Pretty {
+            value: self,
+            symbols,
+        }
+    }
+}
+
+/// the normalized form `(x - 1)/(x + 2) ≥ 0`.
+impl fmt::Display for RationalInequality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} 0", self.function, self.sign)
+    }
+}
+impl fmt::Display for Pretty<'_, RationalInequality> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inequality = self.value;
+        write!(f, "{} ", inequality.function.pretty(self.symbols))?;
+        match self.symbols {
+            Symbols::Unicode => write!(f, "{}", inequality.sign)?,
+            Symbols::Ascii => write!(f, "{}", inequality.sign.ascii())?,
+        }
+        write!(f, " 0")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn function(numerator: Vec<i64>, denominator: Vec<i64>) -> RationalFunction {
+        RationalFunction::new(
+            Polynomial::new("x", numerator),
+            Polynomial::new("x", denominator),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn new_rational_function() {
+        assert!(function(vec![2, 4], vec![2]).is_polynomial());
+        assert_eq!(
+            function(vec![2, 4], vec![2]),
+            RationalFunction::from(Polynomial::new("x", vec![1, 2]))
+        );
+        assert_eq!(
+            RationalFunction::new(Polynomial::new("x", vec![1]), Polynomial::new("x", vec![0])),
+            Err(ErrorKind::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn add_rational_functions() {
+        // x/(x - 3) - 2 = (-x + 6)/(x - 3)
+        let sum = function(vec![0, 1], vec![-3, 1])
+            .add(function(vec![-2], vec![1]))
+            .unwrap();
+        assert_eq!(sum, function(vec![6, -1], vec![-3, 1]));
+        assert_eq!(sum.to_string(), "(-x + 6)/(x - 3)");
+        assert_eq!(function(vec![1], vec![0, 2]).to_string(), "1/(2x)");
+        assert_eq!(function(vec![0, -2], vec![0, 0, 1]).to_string(), "-2x/x^2");
+    }
+
+    #[test]
+    fn find_zeros_and_poles() {
+        // (x - 1)^2 (x + 2) / ((x - 1)(x - 3))
+        let rational = function(vec![2, -3, 0, 1], vec![3, -4, 1]);
+        assert_eq!(
+            rational
+                .zeros()
+                .unwrap()
+                .iter()
+                .map(|root| root.value().to_string())
+                .collect::<Vec<_>>(),
+            ["-2"]
+        );
+        assert_eq!(
+            rational
+                .poles()
+                .unwrap()
+                .iter()
+                .map(Real::to_string)
+                .collect::<Vec<_>>(),
+            ["1", "3"]
+        );
+    }
+
+    #[test]
+    fn solve_rational_inequality() {
+        let solve = |numerator, denominator, sign| {
+            RationalInequality::new(function(numerator, denominator), sign)
+                .solve()
+                .unwrap()
+                .to_string()
+        };
+        // (x - 1)/(x + 2) ≥ 0
+        assert_eq!(solve(vec![-1, 1], vec![2, 1], Sign::Gte), "x < -2 OR x ≥ 1");
+        // (x - 1)^2/(x - 1) ≤ 0, undefined at 1
+        assert_eq!(solve(vec![1, -2, 1], vec![-1, 1], Sign::Lte), "x < 1");
+        // 1/x^2 > 0
+        assert_eq!(
+            solve(vec![1], vec![0, 0, 1], Sign::Gt),
+            "all real number with x ≠ 0"
+        );
+        // x/x ≥ 0
+        assert_eq!(
+            solve(vec![0, 1], vec![0, 1], Sign::Gte),
+            "all real number with x ≠ 0"
+        );
+        // 0/x ≥ 0
+        assert_eq!(
+            solve(vec![0], vec![0, 1], Sign::Gte),
+            "all real number with x ≠ 0"
+        );
+    }
+
+    #[test]
+    fn display_rational_inequality() {
+        let inequality = RationalInequality::new(function(vec![0, 0, 1], vec![-1, 1]), Sign::Lte);
+        assert_eq!(inequality.to_string(), "x^2/(x - 1) ≤ 0");
+        assert_eq!(
+            inequality.pretty(Symbols::Unicode).to_string(),
+            "x²/(x - 1) ≤ 0"
+        );
+        assert_eq!(
+            inequality.pretty(Symbols::Ascii).to_string(),
+            "x^2/(x - 1) <= 0"
+        );
+    }
+}
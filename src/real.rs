@@ -0,0 +1,121 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::surd::Surd;
+
+/// a root of a polynomial: exact if it can be written with at most one square root,
+/// and otherwise a numerical approximation.
+#[derive(Debug, Clone, Copy)]
+pub enum Real {
+    Exact(Surd),
+    /// isolated numerically to about the precision of an `f64`. never NaN.
+    Approx(f64),
+}
+impl Real {
+    pub fn exact(&self) -> Option<Surd> {
+        match self {
+            Self::Exact(value) => Some(*value),
+            Self::Approx(_) => None,
+        }
+    }
+    pub fn is_exact(&self) -> bool {
+        matches!(self, Self::Exact(_))
+    }
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Self::Exact(value) => value.to_f64(),
+            Self::Approx(value) => *value,
+        }
+    }
+    /// the approximate value rounded to six decimal places, without trailing zeros.
+    pub(crate) fn rounded(value: f64) -> String {
+        let rounded = format!("{:.6}", value);
+        let rounded = rounded.trim_end_matches('0').trim_end_matches('.');
+        match rounded {
+            "-0" => "0".to_string(),
+            rounded => rounded.to_string(),
+        }
+    }
+    /// write the number with `√r` and `≈`, or with `sqrt(r)` and `~` if `ascii` is set.
+    fn fmt_with(&self, f: &mut fmt::Formatter<'_>, ascii: bool) -> fmt::Result {
+        match (self, ascii) {
+            (Self::Exact(value), false) => write!(f, "{}", value),
+            (Self::Exact(value), true) => write!(f, "{}", value.ascii()),
+            (Self::Approx(value), false) => write!(f, "≈{}", Self::rounded(*value)),
+            (Self::Approx(value), true) => write!(f, "~{}", Self::rounded(*value)),
+        }
+    }
+    /// the number written with ASCII characters only.
+    pub fn ascii(&self) -> Ascii<'_> {
+        Ascii(self)
+    }
+}
+
+impl From<Surd> for Real {
+    fn from(value: Surd) -> Self {
+        Self::Exact(value)
+    }
+}
+
+/// an approximation is only ever made of a root that has no exact form, so it never
+/// equals an exact number.
+impl PartialEq for Real {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Exact(l), Self::Exact(r)) => l == r,
+            (Self::Approx(l), Self::Approx(r)) => l == r,
+            _ => false,
+        }
+    }
+}
+impl Eq for Real {}
+impl Ord for Real {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Exact(l), Self::Exact(r)) => l.cmp(r),
+            _ => self
+                .to_f64()
+                .total_cmp(&other.to_f64())
+                .then_with(|| other.is_exact().cmp(&self.is_exact())),
+        }
+    }
+}
+impl PartialOrd for Real {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// `√2`, or `≈1.259921` for an approximation.
+impl fmt::Display for Real {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, false)
+    }
+}
+
+/// a [`Real`] displayed with ASCII characters only; see [`Real::ascii`].
+pub struct Ascii<'a>(&'a Real);
+impl fmt::Display for Ascii<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_with(f, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_exact_and_approximate() {
+        let cube_root = Real::Approx(2f64.cbrt());
+        assert!(Real::from(Surd::integer(1)) < cube_root);
+        assert!(cube_root < Real::from(Surd::new(0, 1, 2, 1)));
+        assert_ne!(Real::from(Surd::integer(2)), Real::Approx(2.0));
+    }
+
+    #[test]
+    fn display_real() {
+        assert_eq!(Real::from(Surd::new(0, -1, 2, 1)).to_string(), "-√2");
+        assert_eq!(Real::Approx(2f64.cbrt()).to_string(), "≈1.259921");
+        assert_eq!(Real::Approx(-0.5).ascii().to_string(), "~-0.5");
+    }
+}
@@ -0,0 +1,550 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::{
+    locale::{Locale, Localize},
+    real::Real,
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Bound {
+    Unbounded,
+    Open(Real),
+    Closed(Real),
+}
+impl Bound {
+    pub fn value(&self) -> Option<Real> {
+        match self {
+            Self::Unbounded => None,
+            Self::Open(v) | Self::Closed(v) => Some(*v),
+        }
+    }
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Self::Closed(_))
+    }
+}
+
+/// compare two lower bounds: the one that lets in more numbers is smaller.
+fn cmp_lower(left: &Bound, right: &Bound) -> Ordering {
+    match (left.value(), right.value()) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(l), Some(r)) => l
+            .cmp(&r)
+            .then_with(|| right.is_closed().cmp(&left.is_closed())),
+    }
+}
+
+/// compare two upper bounds: the one that lets in more numbers is greater.
+fn cmp_upper(left: &Bound, right: &Bound) -> Ordering {
+    match (left.value(), right.value()) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(l), Some(r)) => l
+            .cmp(&r)
+            .then_with(|| left.is_closed().cmp(&right.is_closed())),
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Interval {
+    lower: Bound,
+    upper: Bound,
+}
+impl Interval {
+    pub fn new(lower: Bound, upper: Bound) -> Self {
+        Self { lower, upper }
+    }
+    pub fn all() -> Self {
+        Self::new(Bound::Unbounded, Bound::Unbounded)
+    }
+    pub fn point(value: Real) -> Self {
+        Self::new(Bound::Closed(value), Bound::Closed(value))
+    }
+    pub fn lower(&self) -> Bound {
+        self.lower
+    }
+    pub fn upper(&self) -> Bound {
+        self.upper
+    }
+    pub fn is_empty(&self) -> bool {
+        match (self.lower, self.upper) {
+            (Bound::Closed(l), Bound::Closed(u)) => l > u,
+            (l, u) => match (l.value(), u.value()) {
+                (Some(l), Some(u)) => l >= u,
+                _ => false,
+            },
+        }
+    }
+    fn is_point(&self) -> bool {
+        matches!((self.lower, self.upper), (Bound::Closed(l), Bound::Closed(u)) if l == u)
+    }
+    /// whether `next`, which starts no earlier than `self`, overlaps or touches `self`.
+    fn joins(&self, next: &Self) -> bool {
+        match (self.upper, next.lower) {
+            (Bound::Unbounded, _) | (_, Bound::Unbounded) => true,
+            (upper, lower) => {
+                let (u, l) = (upper.value().unwrap(), lower.value().unwrap());
+                u > l || (u == l && (upper.is_closed() || lower.is_closed()))
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SolutionSet {
+    character: String,
+    intervals: Vec<Interval>,
+}
+impl SolutionSet {
+    pub fn empty(character: &str) -> Self {
+        Self {
+            character: character.to_string(),
+            intervals: Vec::new(),
+        }
+    }
+    pub fn all(character: &str) -> Self {
+        Self {
+            character: character.to_string(),
+            intervals: vec![Interval::all()],
+        }
+    }
+    /// build a normalized set: empty intervals are dropped, the rest are sorted and
+    /// overlapping or touching intervals are merged.
+    pub fn from_intervals(character: &str, intervals: Vec<Interval>) -> Self {
+        let mut intervals: Vec<Interval> =
+            intervals.into_iter().filter(|i| !i.is_empty()).collect();
+        intervals.sort_by(|l, r| cmp_lower(&l.lower, &r.lower));
+        let intervals = intervals
+            .into_iter()
+            .fold(Vec::<Interval>::new(), |mut merged, next| {
+                match merged.last_mut() {
+                    Some(last) if last.joins(&next) => {
+                        if cmp_upper(&next.upper, &last.upper) == Ordering::Greater {
+                            last.upper = next.upper;
+                        }
+                    }
+                    _ => merged.push(next),
+                }
+                merged
+            });
+        Self {
+            character: character.to_string(),
+            intervals,
+        }
+    }
+    pub fn character(&self) -> &str {
+        &self.character
+    }
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+    pub fn is_all(&self) -> bool {
+        self.intervals == [Interval::all()]
+    }
+    /// the variable of whichever set has one, as a set solved from a constant has none.
+    fn common_character<'a>(&'a self, other: &'a Self) -> &'a str {
+        match self.character.as_str() {
+            "" => &other.character,
+            character => character,
+        }
+    }
+    /// the numbers in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let intervals = self.intervals.iter().chain(&other.intervals).copied();
+        Self::from_intervals(self.common_character(other), intervals.collect())
+    }
+    /// the numbers in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        for left in &self.intervals {
+            for right in &other.intervals {
+                let lower = match cmp_lower(&left.lower, &right.lower) {
+                    Ordering::Less => right.lower,
+                    _ => left.lower,
+                };
+                let upper = match cmp_upper(&left.upper, &right.upper) {
+                    Ordering::Greater => right.upper,
+                    _ => left.upper,
+                };
+                intervals.push(Interval::new(lower, upper));
+            }
+        }
+        Self::from_intervals(self.common_character(other), intervals)
+    }
+    /// the single excluded point if the set is all real numbers except one.
+    fn punctured_at(&self) -> Option<Real> {
+        match self.intervals.as_slice() {
+            [Interval {
+                lower: Bound::Unbounded,
+                upper: Bound::Open(l),
+            }, Interval {
+                lower: Bound::Open(r),
+                upper: Bound::Unbounded,
+            }] if l == r => Some(*l),
+            _ => None,
+        }
+    }
+    fn fmt_interval(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        interval: &Interval,
+        locale: Locale,
+    ) -> fmt::Result {
+        let character = &self.character;
+        if interval.is_point() {
+            return write!(f, "{} = {}", character, interval.lower.value().unwrap());
+        }
+        let lower_sign = if interval.lower.is_closed() {
+            "≤"
+        } else {
+            "<"
+        };
+        let upper_sign = if interval.upper.is_closed() {
+            "≤"
+        } else {
+            "<"
+        };
+        let greater_sign = if interval.lower.is_closed() {
+            "≥"
+        } else {
+            ">"
+        };
+        match (interval.lower.value(), interval.upper.value()) {
+            (None, None) => write!(f, "{}", locale.all_real()),
+            (None, Some(u)) => write!(f, "{} {} {}", character, upper_sign, u),
+            (Some(l), None) => write!(f, "{} {} {}", character, greater_sign, l),
+            (Some(l), Some(u)) => {
+                write!(f, "{} {} {} {} {}", l, lower_sign, character, upper_sign, u)
+            }
+        }
+    }
+}
+
+impl Localize for SolutionSet {
+    fn fmt_localized(&self, f: &mut fmt::Formatter<'_>, locale: Locale) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "{}", locale.no_solution());
+        }
+        if let Some(point) = self.punctured_at() {
+            return locale.fmt_all_real_except(f, &self.character, &point);
+        }
+        for (i, interval) in self.intervals.iter().enumerate() {
+            if i > 0 {
+                write!(f, " {} ", locale.or())?;
+            }
+            self.fmt_interval(f, interval, locale)?;
+        }
+        Ok(())
+    }
+}
+impl fmt::Display for SolutionSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_localized(f, Locale::English)
+    }
+}
+
+/// the characters interval notation is written with.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Symbols {
+    /// `(-∞, -5) ∪ (2, ∞)`, `∅`
+    Unicode,
+    /// `(-inf, -5) U (2, inf)`, `{}`
+    Ascii,
+}
+
+/// a [`SolutionSet`] displayed in interval notation; see
+/// [`SolutionSet::interval_notation`].
+pub struct IntervalNotation<'a> {
+    set: &'a SolutionSet,
+    symbols: Symbols,
+}
+impl SolutionSet {
+    /// the set as a union of intervals, with a single point written as `{r}`.
+    pub fn interval_notation(&self, symbols: Symbols) -> IntervalNotation<'_> {
+        IntervalNotation { set: self, symbols }
+    }
+}
+impl IntervalNotation<'_> {
+    fn fmt_value(&self, f: &mut fmt::Formatter<'_>, value: &Real) -> fmt::Result {
+        match self.symbols {
+            Symbols::Unicode => write!(f, "{}", value),
+            Symbols::Ascii => write!(f, "{}", value.ascii()),
+        }
+    }
+}
+impl fmt::Display for IntervalNotation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (empty, infinity, union) = match self.symbols {
+            Symbols::Unicode => ("∅", "∞", "∪"),
+            Symbols::Ascii => ("{}", "inf", "U"),
+        };
+        if self.set.is_empty() {
+            return write!(f, "{}", empty);
+        }
+        for (i, interval) in self.set.intervals.iter().enumerate() {
+            if i > 0 {
+                write!(f, " {} ", union)?;
+            }
+            if interval.is_point() {
+                write!(f, "{{")?;
+                self.fmt_value(f, &interval.lower.value().unwrap())?;
+                write!(f, "}}")?;
+                continue;
+            }
+            match interval.lower {
+                Bound::Unbounded => write!(f, "(-{}", infinity)?,
+                Bound::Open(l) => {
+                    write!(f, "(")?;
+                    self.fmt_value(f, &l)?;
+                }
+                Bound::Closed(l) => {
+                    write!(f, "[")?;
+                    self.fmt_value(f, &l)?;
+                }
+            }
+            write!(f, ", ")?;
+            match interval.upper {
+                Bound::Unbounded => write!(f, "{})", infinity)?,
+                Bound::Open(u) => {
+                    self.fmt_value(f, &u)?;
+                    write!(f, ")")?;
+                }
+                Bound::Closed(u) => {
+                    self.fmt_value(f, &u)?;
+                    write!(f, "]")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surd::Surd;
+
+    #[test]
+    fn merge_overlapping_intervals() {
+        assert_eq!(
+            SolutionSet::from_intervals(
+                "x",
+                vec![
+                    Interval::new(Bound::Open(Surd::integer(3).into()), Bound::Unbounded),
+                    Interval::new(
+                        Bound::Closed(Surd::integer(-1).into()),
+                        Bound::Open(Surd::integer(1).into())
+                    ),
+                    Interval::new(
+                        Bound::Closed(Surd::integer(1).into()),
+                        Bound::Closed(Surd::integer(2).into())
+                    ),
+                ]
+            )
+            .intervals(),
+            &[
+                Interval::new(
+                    Bound::Closed(Surd::integer(-1).into()),
+                    Bound::Closed(Surd::integer(2).into())
+                ),
+                Interval::new(Bound::Open(Surd::integer(3).into()), Bound::Unbounded),
+            ]
+        );
+    }
+
+    #[test]
+    fn drop_empty_intervals() {
+        assert!(SolutionSet::from_intervals(
+            "x",
+            vec![
+                Interval::new(
+                    Bound::Open(Surd::integer(1).into()),
+                    Bound::Open(Surd::integer(1).into())
+                ),
+                Interval::new(
+                    Bound::Closed(Surd::integer(2).into()),
+                    Bound::Closed(Surd::integer(1).into())
+                ),
+            ]
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn keep_open_gap() {
+        assert_eq!(
+            SolutionSet::from_intervals(
+                "x",
+                vec![
+                    Interval::new(Bound::Unbounded, Bound::Open(Surd::integer(1).into())),
+                    Interval::new(Bound::Open(Surd::integer(1).into()), Bound::Unbounded),
+                ]
+            )
+            .intervals()
+            .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        let integer = |n| Real::from(Surd::integer(n));
+        // x ≤ 1 OR 3 < x < 5
+        let left = SolutionSet::from_intervals(
+            "x",
+            vec![
+                Interval::new(Bound::Unbounded, Bound::Closed(integer(1))),
+                Interval::new(Bound::Open(integer(3)), Bound::Open(integer(5))),
+            ],
+        );
+        // 1 ≤ x < 4
+        let right = SolutionSet::from_intervals(
+            "",
+            vec![Interval::new(
+                Bound::Closed(integer(1)),
+                Bound::Open(integer(4)),
+            )],
+        );
+        assert_eq!(left.union(&right).to_string(), "x < 5");
+        assert_eq!(left.intersection(&right).to_string(), "x = 1 OR 3 < x < 4");
+        assert!(left.intersection(&SolutionSet::empty("x")).is_empty());
+        assert_eq!(left.intersection(&SolutionSet::all("x")), left);
+    }
+
+    #[test]
+    fn display_solution_set() {
+        assert_eq!(SolutionSet::empty("x").to_string(), "no solution");
+        assert_eq!(SolutionSet::all("x").to_string(), "all real number");
+        assert_eq!(
+            SolutionSet::from_intervals("x", vec![Interval::point(Surd::integer(-2).into())])
+                .to_string(),
+            "x = -2"
+        );
+        assert_eq!(
+            SolutionSet::from_intervals(
+                "x",
+                vec![
+                    Interval::new(Bound::Unbounded, Bound::Open(Surd::integer(-2).into())),
+                    Interval::new(Bound::Open(Surd::integer(-2).into()), Bound::Unbounded),
+                ]
+            )
+            .to_string(),
+            "all real number with x ≠ -2"
+        );
+        assert_eq!(
+            SolutionSet::from_intervals(
+                "x",
+                vec![
+                    Interval::new(Bound::Unbounded, Bound::Closed(Surd::integer(-5).into())),
+                    Interval::new(Bound::Closed(Surd::integer(2).into()), Bound::Unbounded),
+                ]
+            )
+            .to_string(),
+            "x ≤ -5 OR x ≥ 2"
+        );
+        assert_eq!(
+            SolutionSet::from_intervals(
+                "x",
+                vec![Interval::new(
+                    Bound::Closed(Surd::integer(-4).into()),
+                    Bound::Open(Surd::integer(-1).into())
+                )]
+            )
+            .to_string(),
+            "-4 ≤ x < -1"
+        );
+    }
+
+    #[test]
+    fn display_solution_set_in_korean() {
+        let korean = |set: SolutionSet| set.localized(Locale::Korean).to_string();
+        assert_eq!(korean(SolutionSet::empty("x")), "해가 없다");
+        assert_eq!(korean(SolutionSet::all("x")), "모든 실수");
+        assert_eq!(
+            korean(SolutionSet::from_intervals(
+                "x",
+                vec![
+                    Interval::new(Bound::Unbounded, Bound::Open(Surd::integer(-2).into())),
+                    Interval::new(Bound::Open(Surd::integer(-2).into()), Bound::Unbounded),
+                ]
+            )),
+            "x ≠ -2인 모든 실수"
+        );
+        assert_eq!(
+            korean(SolutionSet::from_intervals(
+                "x",
+                vec![
+                    Interval::new(Bound::Unbounded, Bound::Open(Surd::integer(-5).into())),
+                    Interval::new(Bound::Open(Surd::integer(2).into()), Bound::Unbounded),
+                ]
+            )),
+            "x < -5 또는 x > 2"
+        );
+    }
+
+    #[test]
+    fn display_interval_notation() {
+        let both = |set: SolutionSet| {
+            (
+                set.interval_notation(Symbols::Unicode).to_string(),
+                set.interval_notation(Symbols::Ascii).to_string(),
+            )
+        };
+        assert_eq!(
+            both(SolutionSet::empty("x")),
+            ("∅".to_string(), "{}".to_string())
+        );
+        assert_eq!(
+            both(SolutionSet::all("x")),
+            ("(-∞, ∞)".to_string(), "(-inf, inf)".to_string())
+        );
+        assert_eq!(
+            both(SolutionSet::from_intervals(
+                "x",
+                vec![
+                    Interval::new(Bound::Unbounded, Bound::Open(Surd::integer(-5).into())),
+                    Interval::new(Bound::Open(Surd::integer(2).into()), Bound::Unbounded),
+                ]
+            )),
+            (
+                "(-∞, -5) ∪ (2, ∞)".to_string(),
+                "(-inf, -5) U (2, inf)".to_string()
+            )
+        );
+        assert_eq!(
+            both(SolutionSet::from_intervals(
+                "x",
+                vec![
+                    Interval::new(Bound::Unbounded, Bound::Open(Surd::integer(-2).into())),
+                    Interval::new(Bound::Open(Surd::integer(-2).into()), Bound::Unbounded),
+                ]
+            )),
+            (
+                "(-∞, -2) ∪ (-2, ∞)".to_string(),
+                "(-inf, -2) U (-2, inf)".to_string()
+            )
+        );
+        assert_eq!(
+            both(SolutionSet::from_intervals(
+                "x",
+                vec![Interval::new(
+                    Bound::Closed(Surd::new(0, -1, 2, 1).into()),
+                    Bound::Closed(Surd::new(0, 1, 2, 1).into())
+                )]
+            )),
+            ("[-√2, √2]".to_string(), "[-sqrt(2), sqrt(2)]".to_string())
+        );
+        assert_eq!(
+            both(SolutionSet::from_intervals(
+                "x",
+                vec![Interval::point(Surd::rational(1, 2).into())]
+            )),
+            ("{1/2}".to_string(), "{1/2}".to_string())
+        );
+    }
+}
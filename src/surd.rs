@@ -0,0 +1,357 @@
+use std::{cmp::Ordering, convert::TryFrom, fmt};
+
+use crate::{error::ErrorKind, wide::Wide};
+
+/// An exact real number of the form (p + q√r) / s.
+///
+/// The representation is kept normalized: `s > 0`, `r` is square-free, a rational
+/// value has `q == 0` and `r == 1`, and `p`, `q`, `s` share no common factor. Two
+/// equal numbers therefore always compare equal field by field.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Surd {
+    p: i64,
+    q: i64,
+    r: i64,
+    s: i64,
+}
+impl Surd {
+    /// build (p + q√r) / s. `r` must not be negative and `s` must not be zero.
+    ///
+    /// panics if either is, or if normalizing overflows; see [`Surd::checked_new`].
+    pub fn new(p: i64, q: i64, r: i64, s: i64) -> Self {
+        Self::checked_new(p, q, r, s).expect("invalid surd")
+    }
+    /// build (p + q√r) / s. fails with [`ErrorKind::NegativeSquareRoot`] if `r` is
+    /// negative, [`ErrorKind::DivisionByZero`] if `s` is zero, or
+    /// [`ErrorKind::Overflow`] if normalizing overflows.
+    pub fn checked_new(p: i64, q: i64, r: i64, s: i64) -> Result<Self, ErrorKind> {
+        if r < 0 {
+            return Err(ErrorKind::NegativeSquareRoot);
+        }
+        if s == 0 {
+            return Err(ErrorKind::DivisionByZero);
+        }
+        let overflow = || ErrorKind::Overflow;
+        let (k, r) = split_square(r);
+        let (p, q, r) = match (q.checked_mul(k).ok_or_else(overflow)?, r) {
+            (0, _) => (p, 0, 1),
+            (q, 1) => (p.checked_add(q).ok_or_else(overflow)?, 0, 1),
+            (q, r) => (p, q, r),
+        };
+        let (p, q, s) = if s < 0 {
+            (
+                p.checked_neg().ok_or_else(overflow)?,
+                q.checked_neg().ok_or_else(overflow)?,
+                s.checked_neg().ok_or_else(overflow)?,
+            )
+        } else {
+            (p, q, s)
+        };
+        let g = gcd(gcd(p.into(), q.into()), s.into());
+        let reduce = |n: i64| i64::try_from(i128::from(n) / g).map_err(|_| overflow());
+        Ok(Self {
+            p: reduce(p)?,
+            q: reduce(q)?,
+            r,
+            s: reduce(s)?,
+        })
+    }
+    pub fn integer(n: i64) -> Self {
+        Self::new(n, 0, 1, 1)
+    }
+    pub fn rational(numerator: i64, denominator: i64) -> Self {
+        Self::new(numerator, 0, 1, denominator)
+    }
+    /// the two roots (-b ± √d) / 2a of a quadratic with discriminant `d`, smaller first.
+    /// `None` if there is no real root.
+    pub(crate) fn quadratic_roots(
+        a: i64,
+        b: i64,
+        d: i64,
+    ) -> Result<Option<(Self, Self)>, ErrorKind> {
+        if d < 0 {
+            return Ok(None);
+        }
+        let (p, s) = (
+            b.checked_neg().ok_or(ErrorKind::Overflow)?,
+            a.checked_mul(2).ok_or(ErrorKind::Overflow)?,
+        );
+        let minus = Self::checked_new(p, -1, d, s)?;
+        let plus = Self::checked_new(p, 1, d, s)?;
+        Ok(Some((minus.min(plus), minus.max(plus))))
+    }
+    pub fn is_rational(&self) -> bool {
+        self.q == 0
+    }
+    /// the parts (p, q, r, s) of (p + q√r) / s.
+    pub fn parts(&self) -> (i64, i64, i64, i64) {
+        (self.p, self.q, self.r, self.s)
+    }
+    pub fn to_f64(&self) -> f64 {
+        (self.p as f64 + self.q as f64 * (self.r as f64).sqrt()) / self.s as f64
+    }
+    fn wide_parts(&self) -> (Wide, Wide, Wide, Wide) {
+        (
+            Wide::from(self.p),
+            Wide::from(self.q),
+            Wide::from(self.r),
+            Wide::from(self.s),
+        )
+    }
+}
+
+impl Ord for Surd {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // sign of p1/s1 + q1√r1/s1 - p2/s2 - q2√r2/s2, scaled by s1 * s2 > 0
+        let (p1, q1, r1, s1) = self.wide_parts();
+        let (p2, q2, r2, s2) = other.wide_parts();
+        let a = p1.mul(&s2).sub(&p2.mul(&s1));
+        if r1 == r2 {
+            return sign_of(&a, &q1.mul(&s2).sub(&q2.mul(&s1)), &r1);
+        }
+        sign_of_three(&a, &q1.mul(&s2), &r1, &q2.mul(&s1).neg(), &r2)
+    }
+}
+impl PartialOrd for Surd {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Surd {
+    /// write the number with `√r`, or with `sqrt(r)` if `ascii` is set.
+    fn fmt_with(&self, f: &mut fmt::Formatter<'_>, ascii: bool) -> fmt::Result {
+        let Self { p, q, r, s } = *self;
+        if q == 0 {
+            return match s {
+                1 => write!(f, "{}", p),
+                _ => write!(f, "{}/{}", p, s),
+            };
+        }
+        let radical = match (q.abs(), ascii) {
+            (1, false) => format!("√{}", r),
+            (k, false) => format!("{}√{}", k, r),
+            (1, true) => format!("sqrt({})", r),
+            (k, true) => format!("{}*sqrt({})", k, r),
+        };
+        match (p, s) {
+            (0, 1) if q < 0 => write!(f, "-{}", radical),
+            (0, 1) => write!(f, "{}", radical),
+            (0, _) if q < 0 => write!(f, "-{}/{}", radical, s),
+            (0, _) => write!(f, "{}/{}", radical, s),
+            (_, 1) if q < 0 => write!(f, "{} - {}", p, radical),
+            (_, 1) => write!(f, "{} + {}", p, radical),
+            _ if q < 0 => write!(f, "({} - {})/{}", p, radical, s),
+            _ => write!(f, "({} + {})/{}", p, radical, s),
+        }
+    }
+    /// the number written with `sqrt(r)` instead of `√r`.
+    pub fn ascii(&self) -> Ascii<'_> {
+        Ascii(self)
+    }
+}
+
+impl fmt::Display for Surd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, false)
+    }
+}
+
+/// a [`Surd`] displayed with ASCII characters only; see [`Surd::ascii`].
+pub struct Ascii<'a>(&'a Surd);
+impl fmt::Display for Ascii<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_with(f, true)
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    match b {
+        0 => a.abs(),
+        _ => gcd(b, a % b),
+    }
+}
+
+fn isqrt(n: i64) -> i64 {
+    let n = n as i128;
+    let mut root = (n as f64).sqrt() as i128;
+    while root * root > n {
+        root -= 1;
+    }
+    while (root + 1) * (root + 1) <= n {
+        root += 1;
+    }
+    root as i64
+}
+
+/// split n into k²·r with r square-free.
+fn split_square(n: i64) -> (i64, i64) {
+    if n == 0 {
+        return (0, 1);
+    }
+    let (mut k, mut r, mut rest) = (1, 1, n);
+    let mut p = 2;
+    // once p³ exceeds what is left, the rest has at most two prime factors
+    while (p as i128).pow(3) <= rest as i128 {
+        let mut exponent = 0;
+        while rest % p == 0 {
+            rest /= p;
+            exponent += 1;
+        }
+        k *= p.pow(exponent / 2);
+        if exponent % 2 == 1 {
+            r *= p;
+        }
+        p += 1;
+    }
+    let root = isqrt(rest);
+    if root * root == rest {
+        (k * root, r)
+    } else {
+        (k, r * rest)
+    }
+}
+
+/// exact sign of a + b√m.
+fn sign_of(a: &Wide, b: &Wide, m: &Wide) -> Ordering {
+    let (sign_a, sign_b) = (a.signum(), b.signum());
+    if sign_b == Ordering::Equal || m.signum() == Ordering::Equal {
+        return sign_a;
+    }
+    if sign_a == Ordering::Equal || sign_a == sign_b {
+        return sign_b;
+    }
+    match a.mul(a).cmp(&b.mul(b).mul(m)) {
+        Ordering::Greater => sign_a,
+        Ordering::Less => sign_b,
+        Ordering::Equal => Ordering::Equal,
+    }
+}
+
+/// exact sign of a + b√m + c√n.
+fn sign_of_three(a: &Wide, b: &Wide, m: &Wide, c: &Wide, n: &Wide) -> Ordering {
+    let zero = Wide::from(0);
+    let sign_a = a.signum();
+    let (sign_b, sign_c) = (sign_of(&zero, b, m), sign_of(&zero, c, n));
+    let sign_x = match (sign_b, sign_c) {
+        (Ordering::Equal, sign) | (sign, Ordering::Equal) => sign,
+        _ if sign_b == sign_c => sign_b,
+        _ => match b.mul(b).mul(m).cmp(&c.mul(c).mul(n)) {
+            Ordering::Greater => sign_b,
+            Ordering::Less => sign_c,
+            Ordering::Equal => Ordering::Equal,
+        },
+    };
+    if sign_a == Ordering::Equal || sign_a == sign_x {
+        return sign_x;
+    }
+    if sign_x == Ordering::Equal {
+        return sign_a;
+    }
+    // signs differ: compare a² with (b√m + c√n)² = b²m + c²n + 2bc√(mn)
+    let rational = a.mul(a).sub(&b.mul(b).mul(m)).sub(&c.mul(c).mul(n));
+    let irrational = b.mul(c).mul(&Wide::from(-2));
+    match sign_of(&rational, &irrational, &m.mul(n)) {
+        Ordering::Greater => sign_a,
+        Ordering::Less => sign_x,
+        Ordering::Equal => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_surd() {
+        assert_eq!(Surd::new(2, 2, 8, 4), Surd::new(1, 2, 2, 2));
+        assert_eq!(Surd::new(3, 1, 4, 5), Surd::integer(1));
+        assert_eq!(Surd::new(-6, 0, 7, -4), Surd::rational(3, 2));
+        assert_eq!(Surd::new(0, 1, 0, 3), Surd::integer(0));
+    }
+
+    #[test]
+    fn split_square_part() {
+        assert_eq!(split_square(12), (2, 3));
+        assert_eq!(split_square(49), (7, 1));
+        assert_eq!(split_square(30), (1, 30));
+        assert_eq!(split_square(1_000_003 * 1_000_003 * 2), (1_000_003, 2));
+    }
+
+    #[test]
+    fn compare_surds() {
+        let sqrt2 = Surd::new(0, 1, 2, 1);
+        assert!(Surd::rational(141, 100) < sqrt2);
+        assert!(Surd::rational(142, 100) > sqrt2);
+        assert!(Surd::new(0, 1, 3, 1) > sqrt2);
+        assert!(Surd::new(1, 1, 2, 1) < Surd::new(0, 1, 6, 1));
+        assert!(Surd::new(1, 1, 3, 1) < Surd::new(0, 2, 2, 1));
+        assert!(Surd::new(-1, 1, 3, 1) > Surd::new(1, -1, 2, 1));
+    }
+
+    #[test]
+    fn compare_large_surds_exactly() {
+        // convergents of √2 on either side of it, far closer than an f64 can tell
+        let sqrt2 = Surd::new(0, 1, 2, 1);
+        let above = Surd::rational(1_180_872_205_318_713_601, 835_002_744_095_575_440);
+        let below = Surd::rational(2_850_877_693_509_864_481, 2_015_874_949_414_289_041);
+        assert!(below < sqrt2 && sqrt2 < above);
+        let big = i64::MAX;
+        let x = Surd::new(big, big - 1, 2, big - 2);
+        let y = Surd::new(big - 3, big - 2, 3, big - 1);
+        assert_eq!(x.cmp(&y), Ordering::Less);
+        assert_eq!(y.cmp(&x), Ordering::Greater);
+        assert_eq!(x.cmp(&x), Ordering::Equal);
+    }
+
+    #[test]
+    fn reject_invalid_surd() {
+        assert_eq!(
+            Surd::checked_new(1, 1, -2, 1),
+            Err(ErrorKind::NegativeSquareRoot)
+        );
+        assert_eq!(
+            Surd::checked_new(1, 1, 2, 0),
+            Err(ErrorKind::DivisionByZero)
+        );
+        assert_eq!(
+            Surd::checked_new(i64::MIN, 0, 1, -1),
+            Err(ErrorKind::Overflow)
+        );
+        assert_eq!(Surd::checked_new(1, 1, 2, 1), Ok(Surd::new(1, 1, 2, 1)));
+    }
+
+    #[test]
+    fn display_surd() {
+        assert_eq!(Surd::integer(-4).to_string(), "-4");
+        assert_eq!(Surd::rational(-3, 2).to_string(), "-3/2");
+        assert_eq!(Surd::new(0, -1, 2, 1).to_string(), "-√2");
+        assert_eq!(Surd::new(0, 3, 2, 2).to_string(), "3√2/2");
+        assert_eq!(Surd::new(-3, -1, 5, 2).to_string(), "(-3 - √5)/2");
+        assert_eq!(Surd::new(1, 1, 5, 1).to_string(), "1 + √5");
+    }
+
+    #[test]
+    fn display_surd_in_ascii() {
+        assert_eq!(Surd::rational(-3, 2).ascii().to_string(), "-3/2");
+        assert_eq!(Surd::new(0, -1, 2, 1).ascii().to_string(), "-sqrt(2)");
+        assert_eq!(Surd::new(0, 3, 2, 2).ascii().to_string(), "3*sqrt(2)/2");
+        assert_eq!(
+            Surd::new(-3, -1, 5, 2).ascii().to_string(),
+            "(-3 - sqrt(5))/2"
+        );
+    }
+
+    #[test]
+    fn roots_of_quadratic() {
+        assert_eq!(
+            Surd::quadratic_roots(1, 0, 8),
+            Ok(Some((Surd::new(0, -1, 2, 1), Surd::new(0, 1, 2, 1))))
+        );
+        assert_eq!(
+            Surd::quadratic_roots(-1, 5, 9),
+            Ok(Some((Surd::integer(1), Surd::integer(4))))
+        );
+        assert_eq!(Surd::quadratic_roots(1, 0, -4), Ok(None));
+    }
+}
@@ -1,155 +1,664 @@
-use std::{cmp::Ordering, ops::Add};
+use std::{cmp::Ordering, convert::TryFrom, fmt};
 
-use crate::error::{Error, Result};
+use crate::{
+    error::ErrorKind,
+    explanation::{Explanation, Region, Step},
+    real::Real,
+    solution::{Bound, Interval, SolutionSet, Symbols},
+    surd::Surd,
+    wide::Wide,
+};
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-pub(crate) struct Number(i32);
+/// an exact rational number, kept reduced with a positive denominator.
+///
+/// arithmetic is done in 128 bits and fails with [`ErrorKind::Overflow`] if the reduced
+/// result does not fit back into 64 bits.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Number {
+    numerator: i64,
+    denominator: i64,
+}
 impl Number {
-    pub(crate) fn new(input: i32) -> Self {
-        Number(input)
-    }
-    pub(crate) fn new_with_default(input: Option<&str>, default: i32) -> Self {
-        let num = match input {
-            Some(degree) => match degree.parse::<i32>() {
-                Ok(degree) => degree,
-                Err(_) => match degree.chars().next() {
-                    Some('-') => -default,
-                    _ => default,
-                },
-            },
-            None => default,
-        };
-        Number(num)
+    #[cfg(test)]
+    pub(crate) fn new(numerator: i64, denominator: i64) -> Self {
+        Self::reduce(numerator.into(), denominator.into()).unwrap()
     }
-    pub(crate) fn set_sign(&mut self, sign: &str) {
-        let val = self.0.abs();
-        self.0 = match sign {
-            "-" => -val,
-            _ => val,
-        };
+    fn reduce(numerator: i128, denominator: i128) -> Result<Self, ErrorKind> {
+        if denominator == 0 {
+            return Err(ErrorKind::DivisionByZero);
+        }
+        let g = gcd(numerator, denominator) * denominator.signum();
+        Ok(Self {
+            numerator: i64::try_from(numerator / g).map_err(|_| ErrorKind::Overflow)?,
+            denominator: i64::try_from(denominator / g).map_err(|_| ErrorKind::Overflow)?,
+        })
+    }
+    /// `numerator/denominator`, reduced. fails with [`ErrorKind::DivisionByZero`] if
+    /// the denominator is zero.
+    pub fn from_fraction(numerator: i64, denominator: i64) -> Result<Self, ErrorKind> {
+        Self::reduce(numerator.into(), denominator.into())
+    }
+    /// the decimal `integer.fraction`, converted exactly.
+    pub(crate) fn from_decimal(integer: &str, fraction: &str) -> Result<Self, ErrorKind> {
+        let numerator = format!("{}{}", integer, fraction)
+            .parse::<i128>()
+            .map_err(|_| ErrorKind::Overflow)?;
+        let denominator = 10_i128
+            .checked_pow(fraction.len() as u32)
+            .ok_or(ErrorKind::Overflow)?;
+        Self::reduce(numerator, denominator)
+    }
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+    /// whether the number is less than, equal to or greater than zero.
+    pub fn signum(&self) -> Ordering {
+        self.numerator.cmp(&0)
+    }
+    /// the numerator, which carries the sign.
+    pub fn numerator(&self) -> i64 {
+        self.numerator
+    }
+    /// the denominator, always positive.
+    pub fn denominator(&self) -> i64 {
+        self.denominator
+    }
+    /// the value as an integer. only meaningful when the denominator is 1.
+    fn to_integer(self) -> i64 {
+        debug_assert_eq!(self.denominator, 1);
+        self.numerator
+    }
+    pub(crate) fn to_f64(self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+    pub(crate) fn to_surd(self) -> Surd {
+        Surd::rational(self.numerator, self.denominator)
+    }
+    fn wide(self) -> (i128, i128) {
+        (self.numerator.into(), self.denominator.into())
+    }
+    pub(crate) fn checked_add(self, rhs: Self) -> Result<Self, ErrorKind> {
+        let ((n1, d1), (n2, d2)) = (self.wide(), rhs.wide());
+        let numerator = (n1 * d2).checked_add(n2 * d1).ok_or(ErrorKind::Overflow)?;
+        Self::reduce(numerator, d1 * d2)
+    }
+    pub(crate) fn checked_sub(self, rhs: Self) -> Result<Self, ErrorKind> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+    pub(crate) fn checked_mul(self, rhs: Self) -> Result<Self, ErrorKind> {
+        let ((n1, d1), (n2, d2)) = (self.wide(), rhs.wide());
+        Self::reduce(n1 * n2, d1 * d2)
+    }
+    /// fails with [`ErrorKind::DivisionByZero`] if `rhs` is zero.
+    pub(crate) fn checked_div(self, rhs: Self) -> Result<Self, ErrorKind> {
+        let ((n1, d1), (n2, d2)) = (self.wide(), rhs.wide());
+        Self::reduce(n1 * d2, d1 * n2)
+    }
+    pub(crate) fn checked_neg(self) -> Result<Self, ErrorKind> {
+        Ok(Self {
+            numerator: self.numerator.checked_neg().ok_or(ErrorKind::Overflow)?,
+            denominator: self.denominator,
+        })
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub(crate) struct Monomial<'a> {
-    coefficient: Number,
-    character: Option<&'a str>,
-    degree: Number,
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            d => write!(f, "{}/{}", self.numerator, d),
+        }
+    }
 }
-impl<'a> Monomial<'a> {
-    pub(crate) fn new(coefficient: Number, character: Option<&'a str>, degree: Number) -> Self {
+
+impl From<i64> for Number {
+    fn from(n: i64) -> Self {
         Self {
-            coefficient,
-            character,
-            degree,
+            numerator: n,
+            denominator: 1,
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub(crate) struct Quadratic {
+fn gcd(a: i128, b: i128) -> i128 {
+    match b {
+        0 => a.abs(),
+        _ => gcd(b, a % b),
+    }
+}
+
+fn lcm(a: i64, b: i64) -> Result<i64, ErrorKind> {
+    let g = gcd(a.into(), b.into()) as i64;
+    (a / g).checked_mul(b).ok_or(ErrorKind::Overflow)
+}
+
+/// the remainder of `a` divided by `b` over the integers, scaled by the leading
+/// coefficient of `b` to the power of one more than the difference in degree so that
+/// every division is exact. `a` must not be of lower degree than `b`.
+fn pseudo_remainder(a: &[Wide], b: &[Wide]) -> Vec<Wide> {
+    let leading = &b[b.len() - 1];
+    let mut remainder = a.to_vec();
+    for shift in (0..=a.len() - b.len()).rev() {
+        let factor = remainder[shift + b.len() - 1].clone();
+        for c in remainder.iter_mut() {
+            *c = c.mul(leading);
+        }
+        for (j, c) in b.iter().enumerate() {
+            remainder[shift + j] = remainder[shift + j].sub(&factor.mul(c));
+        }
+    }
+    trim_zeros(&mut remainder);
+    remainder
+}
+
+/// drop the zero coefficients of the highest powers.
+fn trim_zeros(coefficients: &mut Vec<Wide>) {
+    while matches!(coefficients.last(), Some(c) if c.is_zero()) {
+        coefficients.pop();
+    }
+}
+
+/// the highest degree an expanded polynomial may reach.
+const MAX_DEGREE: usize = 64;
+
+/// a polynomial of any degree in one variable, expanded from whatever the parser
+/// read. `coefficients[n]` is the coefficient of the n-th power.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Polynomial {
     character: String,
-    a: i32,
-    b: i32,
-    c: i32,
+    coefficients: Vec<Number>,
 }
-impl Quadratic {
+impl Polynomial {
+    /// the polynomial with `coefficients[n]` as the coefficient of `character^n`.
+    pub fn from_coefficients(character: impl Into<String>, coefficients: Vec<Number>) -> Self {
+        Self {
+            character: character.into(),
+            coefficients,
+        }
+    }
     #[cfg(test)]
-    pub(crate) fn new(character: String, a: i32, b: i32, c: i32) -> Self {
-        Self { character, a, b, c }
+    pub(crate) fn new(character: &str, coefficients: Vec<i64>) -> Self {
+        Self {
+            character: character.to_string(),
+            coefficients: coefficients.into_iter().map(Number::from).collect(),
+        }
     }
-    pub(crate) fn from_monomials(monomials: Vec<Monomial>) -> Result<Self> {
-        let character: Option<&str> =
-            monomials
-                .iter()
-                .fold(Ok(None), |old, Monomial { character: new, .. }| match new {
-                    None => old,
-                    Some(new) => match old {
-                        Err(_) => old,
-                        Ok(None) => Ok(Some(*new)),
-                        Ok(Some(old)) => {
-                            if *new != old {
-                                Err(Error::InvalidCharacter {
-                                    expected: old.to_string(),
-                                    found: new.to_string(),
-                                })
-                            } else {
-                                Ok(Some(old))
-                            }
-                        }
-                    },
-                })?;
-        let (a, b, c) = monomials
+    pub(crate) fn constant(value: Number) -> Self {
+        Self {
+            character: String::new(),
+            coefficients: vec![value],
+        }
+    }
+    pub(crate) fn variable(character: &str) -> Self {
+        Self {
+            character: character.to_string(),
+            coefficients: vec![Number::from(0), Number::from(1)],
+        }
+    }
+    /// the variable, or `""` for a constant.
+    pub fn character(&self) -> &str {
+        &self.character
+    }
+    /// the highest power with a nonzero coefficient, or 0 for a constant.
+    pub fn degree(&self) -> usize {
+        self.coefficients
             .iter()
-            .try_fold((0, 0, 0), |(a, b, c), monomial| {
-                let val = monomial.coefficient.0;
-                match (monomial.character, monomial.degree) {
-                    (Some(_), Number(2)) => Ok((a + val, b, c)),
-                    (Some(_), Number(1)) => Ok((a, b + val, c)),
-                    (None, Number(1)) => Ok((a, b, c + val)),
-                    _ => Err(Error::InvalidQuadratic),
-                }
-            })?;
+            .rposition(|c| !c.is_zero())
+            .unwrap_or(0)
+    }
+    /// the coefficient of the `degree`-th power.
+    pub fn coefficient(&self, degree: usize) -> Number {
+        self.coefficients
+            .get(degree)
+            .copied()
+            .unwrap_or_else(|| Number::from(0))
+    }
+    /// the coefficient of the highest power.
+    pub fn leading(&self) -> Number {
+        self.coefficient(self.degree())
+    }
+    /// the (coefficient, power) of each nonzero term, highest power first.
+    pub fn terms(&self) -> impl Iterator<Item = (Number, u32)> + '_ {
+        self.coefficients
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, coefficient)| !coefficient.is_zero())
+            .map(|(power, coefficient)| (*coefficient, power as u32))
+    }
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.iter().all(Number::is_zero)
+    }
+    /// the variable shared by both operands; a constant fits with any variable.
+    fn common_character(&self, rhs: &Self) -> Result<String, ErrorKind> {
+        match (self.character.as_str(), rhs.character.as_str()) {
+            ("", character) | (character, "") => Ok(character.to_string()),
+            (left, right) if left != right => Err(ErrorKind::InvalidCharacter {
+                expected: left.to_string(),
+                found: right.to_string(),
+            }),
+            (character, _) => Ok(character.to_string()),
+        }
+    }
+    pub(crate) fn negate(self) -> Result<Self, ErrorKind> {
         Ok(Self {
-            character: character.unwrap_or("").to_string(),
-            a,
-            b,
-            c,
+            coefficients: self
+                .coefficients
+                .into_iter()
+                .map(Number::checked_neg)
+                .collect::<Result<_, ErrorKind>>()?,
+            character: self.character,
         })
     }
-    fn reverse(&mut self) {
-        self.a *= -1;
-        self.b *= -1;
-        self.c *= -1;
+    pub(crate) fn add(self, rhs: Self) -> Result<Self, ErrorKind> {
+        let character = self.common_character(&rhs)?;
+        let len = self.coefficients.len().max(rhs.coefficients.len());
+        Ok(Self {
+            character,
+            coefficients: (0..len)
+                .map(|n| self.coefficient(n).checked_add(rhs.coefficient(n)))
+                .collect::<Result<_, ErrorKind>>()?,
+        })
     }
-    fn get_d(&self) -> i32 {
-        self.b.pow(2) - 4 * &self.a * self.c
+    pub(crate) fn mul(self, rhs: Self) -> Result<Self, ErrorKind> {
+        let character = self.common_character(&rhs)?;
+        if self.degree() + rhs.degree() > MAX_DEGREE {
+            return Err(ErrorKind::DegreeTooHigh);
+        }
+        let mut coefficients = vec![Number::from(0); self.degree() + rhs.degree() + 1];
+        for (i, left) in self.coefficients.iter().enumerate().take(self.degree() + 1) {
+            for (j, right) in rhs.coefficients.iter().enumerate().take(rhs.degree() + 1) {
+                coefficients[i + j] = coefficients[i + j].checked_add(left.checked_mul(*right)?)?;
+            }
+        }
+        Ok(Self {
+            character,
+            coefficients,
+        })
     }
-    fn get_solution(&self) -> (f32, f32) {
-        let solution1 = ((-self.b) as f32 - ((self.b.pow(2) - 4 * self.a * self.c) as f32).sqrt())
-            / (2 * &self.a) as f32;
-        let solution2 = ((-self.b) as f32 + ((self.b.pow(2) - 4 * self.a * self.c) as f32).sqrt())
-            / (2 * &self.a) as f32;
-
-        if solution1 < solution2 {
-            (solution1, solution2)
-        } else {
-            (solution2, solution1)
+    /// exponentiation by squaring, so a huge exponent fails fast instead of looping.
+    pub(crate) fn pow(self, exponent: u32) -> Result<Self, ErrorKind> {
+        let mut result = Self {
+            character: self.character.clone(),
+            coefficients: vec![Number::from(1)],
+        };
+        let (mut base, mut exponent) = (self, exponent);
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result.mul(base.clone())?;
+            }
+            exponent /= 2;
+            if exponent > 0 {
+                base = base.clone().mul(base)?;
+            }
         }
+        Ok(result)
+    }
+    pub(crate) fn derivative(&self) -> Result<Self, ErrorKind> {
+        let coefficients = (1..=self.degree().max(1))
+            .map(|n| self.coefficient(n).checked_mul(Number::from(n as i64)))
+            .collect::<Result<_, ErrorKind>>()?;
+        Ok(Self {
+            character: self.character.clone(),
+            coefficients,
+        })
+    }
+    /// the quotient and the remainder of dividing by `divisor`.
+    pub(crate) fn div_rem(&self, divisor: &Self) -> Result<(Self, Self), ErrorKind> {
+        let (n, d) = (self.degree(), divisor.degree());
+        let leading = divisor.leading();
+        if leading.is_zero() {
+            return Err(ErrorKind::DivisionByZero);
+        }
+        let mut remainder: Vec<Number> = (0..=n).map(|i| self.coefficient(i)).collect();
+        let mut quotient = vec![Number::from(0); n.saturating_sub(d) + 1];
+        for i in (0..=n.saturating_sub(d)).rev().filter(|_| n >= d) {
+            let factor = remainder[i + d].checked_div(leading)?;
+            quotient[i] = factor;
+            for j in 0..=d {
+                remainder[i + j] =
+                    remainder[i + j].checked_sub(factor.checked_mul(divisor.coefficient(j))?)?;
+            }
+        }
+        remainder.truncate(d.max(1));
+        Ok((
+            Self::from_coefficients(self.character.clone(), quotient),
+            Self::from_coefficients(self.character.clone(), remainder),
+        ))
+    }
+    /// the same polynomial divided by its leading coefficient.
+    pub(crate) fn monic(&self) -> Result<Self, ErrorKind> {
+        let leading = self.leading();
+        if leading.is_zero() {
+            return Ok(self.clone());
+        }
+        Ok(Self {
+            character: self.character.clone(),
+            coefficients: (0..=self.degree())
+                .map(|n| self.coefficient(n).checked_div(leading))
+                .collect::<Result<_, ErrorKind>>()?,
+        })
+    }
+    /// the monic greatest common divisor, by the subresultant remainder sequence over
+    /// the integers: each pseudo-remainder is divided by a factor known to divide it,
+    /// which keeps the coefficients to about the size of a determinant of the inputs.
+    pub(crate) fn gcd(&self, other: &Self) -> Result<Self, ErrorKind> {
+        let character = self.common_character(other)?;
+        let integers = |polynomial: &Self| -> Result<Vec<Wide>, ErrorKind> {
+            let mut coefficients: Vec<Wide> = polynomial
+                .primitive()?
+                .coefficients
+                .iter()
+                .map(|c| Wide::from(c.to_integer()))
+                .collect();
+            trim_zeros(&mut coefficients);
+            Ok(coefficients)
+        };
+        let (mut a, mut b) = (integers(self)?, integers(other)?);
+        if a.len() < b.len() {
+            std::mem::swap(&mut a, &mut b);
+        }
+        let (mut g, mut h) = (Wide::from(1), Wide::from(1));
+        while !b.is_empty() {
+            let delta = a.len() - b.len();
+            let remainder = pseudo_remainder(&a, &b);
+            let divisor = g.mul(&h.pow(delta));
+            a = b;
+            b = remainder.iter().map(|c| c.div_rem(&divisor).0).collect();
+            g = a[a.len() - 1].clone();
+            h = match delta {
+                0 => h,
+                _ => g.pow(delta).div_rem(&h.pow(delta - 1)).0,
+            };
+        }
+        let content = a.iter().fold(Wide::from(0), |content, c| content.gcd(c));
+        let coefficients = a
+            .iter()
+            .map(|c| {
+                let n = i64::try_from(&c.div_rem(&content).0).map_err(|_| ErrorKind::Overflow)?;
+                Ok(Number::from(n))
+            })
+            .collect::<Result<_, ErrorKind>>()?;
+        Self::from_coefficients(character, coefficients).monic()
+    }
+    /// the value at `x`, by Horner's method.
+    pub(crate) fn evaluate(&self, x: Number) -> Result<Number, ErrorKind> {
+        (0..=self.degree())
+            .rev()
+            .try_fold(Number::from(0), |value, n| {
+                value.checked_mul(x)?.checked_add(self.coefficient(n))
+            })
+    }
+    /// the same roots with integer coefficients that have no common factor, and a
+    /// positive leading coefficient.
+    pub(crate) fn primitive(&self) -> Result<Self, ErrorKind> {
+        let scale = self
+            .coefficients
+            .iter()
+            .map(Number::denominator)
+            .try_fold(1, lcm)?;
+        let integers = self
+            .coefficients
+            .iter()
+            .map(|c| Ok(c.checked_mul(Number::from(scale))?.to_integer()))
+            .collect::<Result<Vec<i64>, ErrorKind>>()?;
+        let content = integers.iter().fold(0, |g, &n| gcd(g, n.into())).max(1);
+        let content = match self.leading().signum() {
+            Ordering::Less => -content,
+            _ => content,
+        };
+        Ok(Self {
+            character: self.character.clone(),
+            coefficients: integers
+                .into_iter()
+                .map(|n| Number::reduce(n.into(), content))
+                .collect::<Result<_, ErrorKind>>()?,
+        })
     }
 }
 
-impl Add for Quadratic {
-    type Output = Self;
+impl From<&Quadratic> for Polynomial {
+    fn from(quadratic: &Quadratic) -> Self {
+        Self {
+            character: quadratic.character.clone(),
+            coefficients: vec![quadratic.c, quadratic.b, quadratic.a],
+        }
+    }
+}
 
-    fn add(self, rhs: Self) -> Self::Output {
+/// `ax^2 + bx + c` in the variable `character`. any of the coefficients may be zero,
+/// so this also holds linear and constant expressions.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Quadratic {
+    character: String,
+    a: Number,
+    b: Number,
+    c: Number,
+}
+impl Quadratic {
+    /// `ax^2 + bx + c` in `character`, which may be empty if `a` and `b` are zero.
+    pub fn new(
+        character: impl Into<String>,
+        a: impl Into<Number>,
+        b: impl Into<Number>,
+        c: impl Into<Number>,
+    ) -> Self {
         Self {
+            character: character.into(),
+            a: a.into(),
+            b: b.into(),
+            c: c.into(),
+        }
+    }
+    pub(crate) fn from_polynomial(polynomial: Polynomial) -> Result<Self, ErrorKind> {
+        if polynomial.degree() > 2 {
+            return Err(ErrorKind::InvalidQuadratic);
+        }
+        Ok(Self {
+            a: polynomial.coefficient(2),
+            b: polynomial.coefficient(1),
+            c: polynomial.coefficient(0),
+            character: polynomial.character,
+        })
+    }
+    /// the variable, or `""` for a constant.
+    pub fn character(&self) -> &str {
+        &self.character
+    }
+    /// the coefficient of `x^2`.
+    pub fn a(&self) -> Number {
+        self.a
+    }
+    /// the coefficient of `x`.
+    pub fn b(&self) -> Number {
+        self.b
+    }
+    /// the constant term.
+    pub fn c(&self) -> Number {
+        self.c
+    }
+    /// the (coefficient, power) of each nonzero term, highest power first.
+    pub fn terms(&self) -> impl Iterator<Item = (Number, u32)> {
+        vec![(self.a, 2), (self.b, 1), (self.c, 0)]
+            .into_iter()
+            .filter(|(coefficient, _)| !coefficient.is_zero())
+    }
+    pub fn is_zero(&self) -> bool {
+        self.a.is_zero() && self.b.is_zero() && self.c.is_zero()
+    }
+    fn reverse(self) -> Result<Self, ErrorKind> {
+        Ok(Self {
+            a: self.a.checked_neg()?,
+            b: self.b.checked_neg()?,
+            c: self.c.checked_neg()?,
             character: self.character,
-            a: self.a + rhs.a,
-            b: self.b + rhs.b,
-            c: self.c + rhs.c,
+        })
+    }
+    fn add(self, rhs: Self) -> Result<Self, ErrorKind> {
+        Ok(Self {
+            character: if self.character.is_empty() {
+                rhs.character
+            } else {
+                self.character
+            },
+            a: self.a.checked_add(rhs.a)?,
+            b: self.b.checked_add(rhs.b)?,
+            c: self.c.checked_add(rhs.c)?,
+        })
+    }
+    /// the discriminant `b^2 - 4ac`.
+    pub fn discriminant(&self) -> Result<Number, ErrorKind> {
+        self.b
+            .checked_mul(self.b)?
+            .checked_sub(Number::from(4).checked_mul(self.a)?.checked_mul(self.c)?)
+    }
+    /// the distinct real roots, smaller first. a constant has none.
+    pub fn roots(&self) -> Result<Vec<Surd>, ErrorKind> {
+        if self.a.is_zero() {
+            if self.b.is_zero() {
+                return Ok(vec![]);
+            }
+            return Ok(vec![self.c.checked_neg()?.checked_div(self.b)?.to_surd()]);
         }
+        Ok(match self.get_solution()? {
+            Some((s1, s2)) if s1 == s2 => vec![s1],
+            Some((s1, s2)) => vec![s1, s2],
+            None => vec![],
+        })
+    }
+    fn get_solution(&self) -> Result<Option<(Surd, Surd)>, ErrorKind> {
+        // scaling every coefficient by a common denominator keeps the roots and
+        // makes them integers
+        let scale = Number::from(
+            [self.a, self.b, self.c]
+                .iter()
+                .map(Number::denominator)
+                .try_fold(1, lcm)?,
+        );
+        Surd::quadratic_roots(
+            self.a.checked_mul(scale)?.to_integer(),
+            self.b.checked_mul(scale)?.to_integer(),
+            self.discriminant()?
+                .checked_mul(scale)?
+                .checked_mul(scale)?
+                .to_integer(),
+        )
     }
 }
 
+/// `n` in superscript digits, `²` for 2.
+pub(crate) fn superscript(n: u32) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    n.to_string()
+        .chars()
+        .map(|digit| DIGITS[digit.to_digit(10).unwrap() as usize])
+        .collect()
+}
+
+/// write out `terms` of a polynomial in `character`, highest power first, with zero
+/// terms left out and unit coefficients implied, or `0` if there are none. powers are
+/// written `x^2`, or `x²` if `superscript` is set.
+fn fmt_terms(
+    f: &mut fmt::Formatter<'_>,
+    character: &str,
+    terms: impl Iterator<Item = (Number, u32)>,
+    superscript: bool,
+) -> fmt::Result {
+    let mut empty = true;
+    for (coefficient, power) in terms {
+        let negative = coefficient.signum() == Ordering::Less;
+        match (empty, negative) {
+            (true, true) => write!(f, "-")?,
+            (true, false) => {}
+            (false, true) => write!(f, " - ")?,
+            (false, false) => write!(f, " + ")?,
+        }
+        let (numerator, denominator) = (
+            coefficient.numerator.unsigned_abs(),
+            coefficient.denominator,
+        );
+        match (numerator, denominator) {
+            (1, 1) if power > 0 => {}
+            (n, 1) => write!(f, "{}", n)?,
+            (n, d) => write!(f, "{}/{}", n, d)?,
+        }
+        match (power, superscript) {
+            (0, _) => {}
+            (1, _) => write!(f, "{}", character)?,
+            (n, false) => write!(f, "{}^{}", character, n)?,
+            (n, true) => write!(f, "{}{}", character, self::superscript(n))?,
+        }
+        empty = false;
+    }
+    if empty {
+        write!(f, "0")?;
+    }
+    Ok(())
+}
+
+impl Quadratic {
+    /// write the quadratic with `x^2`, or with `x²` if `superscript` is set.
+    fn fmt_with(&self, f: &mut fmt::Formatter<'_>, superscript: bool) -> fmt::Result {
+        fmt_terms(f, &self.character, self.terms(), superscript)
+    }
+    /// the quadratic written with `symbols`: `x² ...` or plain `x^2 ...`.
+    pub fn pretty(&self, symbols: Symbols) -> Pretty<'_, Self> {
+        Pretty {
+            value: self,
+            symbols,
+        }
+    }
+}
+
+/// `ax^2 + bx + c` with zero terms left out and unit coefficients implied, or `0`.
+impl fmt::Display for Quadratic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, false)
+    }
+}
+
+impl Polynomial {
+    /// the polynomial written with `symbols`: `x³ ...` or plain `x^3 ...`.
+    pub fn pretty(&self, symbols: Symbols) -> Pretty<'_, Self> {
+        Pretty {
+            value: self,
+            symbols,
+        }
+    }
+}
+
+/// `x^3 - 4x` with zero terms left out and unit coefficients implied, or `0`.
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_terms(f, &self.character, self.terms(), false)
+    }
+}
+
+/// the comparison between the two sides of an inequality.
 #[derive(Debug, PartialEq, Clone)]
-pub(crate) enum Sign {
+pub enum Sign {
+    /// `<`
     Lt,
+    /// `≤`, typed `<=`
     Lte,
+    /// `>`
     Gt,
+    /// `≥`, typed `>=`
     Gte,
 }
 impl Sign {
-    pub(crate) fn new(s: &str) -> Result<Self> {
+    /// the sign typed as `s`: `<`, `<=`, `≤`, `>`, `>=` or `≥`.
+    pub fn new(s: &str) -> Result<Self, ErrorKind> {
         match s {
             "<" => Ok(Self::Lt),
             "<=" | "≤" => Ok(Self::Lte),
             ">" => Ok(Self::Gt),
             ">=" | "≥" => Ok(Self::Gte),
-            k => Err(Error::InvalidIneqSign(k.to_string())),
+            k => Err(ErrorKind::InvalidIneqSign(k.to_string())),
         }
     }
-    fn reverse(&self) -> Self {
+    /// the sign after multiplying both sides by a negative number.
+    pub fn reverse(&self) -> Self {
         match self {
             Self::Lt => Self::Gt,
             Self::Lte => Self::Gte,
@@ -157,53 +666,280 @@ impl Sign {
             Self::Gte => Self::Lte,
         }
     }
+    /// whether the sign lets in the values where both sides are equal.
+    pub fn is_inclusive(&self) -> bool {
+        matches!(self, Self::Lte | Self::Gte)
+    }
+    /// whether `lhs sign rhs` holds when lhs compares to rhs as `ordering`.
+    pub fn holds(&self, ordering: Ordering) -> bool {
+        match self {
+            Self::Lt => ordering == Ordering::Less,
+            Self::Lte => ordering != Ordering::Greater,
+            Self::Gt => ordering == Ordering::Greater,
+            Self::Gte => ordering != Ordering::Less,
+        }
+    }
+}
+
+impl Sign {
+    /// the sign as typed on a keyboard, `<=` for `≤`.
+    pub(crate) fn ascii(&self) -> &'static str {
+        match self {
+            Self::Lt => "<",
+            Self::Lte => "<=",
+            Self::Gt => ">",
+            Self::Gte => ">=",
+        }
+    }
 }
 
+impl fmt::Display for Sign {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = match self {
+            Self::Lt => "<",
+            Self::Lte => "≤",
+            Self::Gt => ">",
+            Self::Gte => "≥",
+        };
+        write!(f, "{}", sign)
+    }
+}
+
+/// `quadratic sign 0`, with every term moved to the left-hand side.
 #[derive(Debug, PartialEq)]
-pub(crate) struct QuadraticInequality {
+pub struct QuadraticInequality {
     quadratic: Quadratic,
     sign: Sign,
 }
 impl QuadraticInequality {
-    #[cfg(test)]
-    pub(crate) fn new(quadratic: Quadratic, sign: Sign) -> Self {
+    /// `quadratic sign 0`.
+    pub fn new(quadratic: Quadratic, sign: Sign) -> Self {
         Self { quadratic, sign }
     }
-    pub(crate) fn from_expr(input: (Quadratic, Sign, Quadratic)) -> Self {
-        let (left, sign, mut right) = input;
-        right.reverse();
-        Self {
-            quadratic: left + right,
+    /// `left sign right`, with `right` moved over to the left-hand side. fails if the
+    /// sides are in different variables, or if a coefficient overflows.
+    pub fn from_sides(left: Quadratic, sign: Sign, right: Quadratic) -> Result<Self, ErrorKind> {
+        Self::from_expr((left, sign, right))
+    }
+    pub(crate) fn from_expr(input: (Quadratic, Sign, Quadratic)) -> Result<Self, ErrorKind> {
+        let (left, sign, right) = input;
+        if !left.character.is_empty()
+            && !right.character.is_empty()
+            && left.character != right.character
+        {
+            return Err(ErrorKind::InvalidCharacter {
+                expected: left.character,
+                found: right.character,
+            });
+        }
+        Ok(Self {
+            quadratic: left.add(right.reverse()?)?,
             sign,
+        })
+    }
+    /// everything moved to the left-hand side.
+    pub fn quadratic(&self) -> &Quadratic {
+        &self.quadratic
+    }
+    pub fn sign(&self) -> &Sign {
+        &self.sign
+    }
+    /// the values of the variable for which the inequality holds.
+    pub fn solve(&self) -> Result<SolutionSet, ErrorKind> {
+        self.solve_with(&mut Vec::new())
+    }
+    /// the solution worked out step by step, starting from the normalized form.
+    pub fn explain(&self) -> Result<Explanation, ErrorKind> {
+        self.explain_with(Vec::new())
+    }
+    fn explain_with(&self, mut steps: Vec<Step>) -> Result<Explanation, ErrorKind> {
+        steps.push(Step::CollectTerms {
+            polynomial: Polynomial::from(&self.quadratic),
+            sign: self.sign.clone(),
+        });
+        let solution = self.solve_with(&mut steps)?;
+        steps.push(Step::Answer(solution));
+        Ok(Explanation::new(steps))
+    }
+    /// the solution, with the steps taken to reach it pushed onto `steps`.
+    pub(crate) fn solve_with(&self, steps: &mut Vec<Step>) -> Result<SolutionSet, ErrorKind> {
+        match (self.quadratic.a.is_zero(), self.quadratic.b.is_zero()) {
+            (true, true) => Ok(self.get_constant_solution(steps)),
+            (true, false) => self.get_linear_solution(steps),
+            (false, _) => self.get_quadratic_solution(steps),
         }
     }
-    pub(crate) fn get_solution(&self) -> String {
-        let d = self.quadratic.get_d();
-        let (s1, s2) = &self.quadratic.get_solution();
-        let sign = if self.quadratic.a > 0 {
-            self.sign.clone()
+    /// `c sign 0` is either always or never true.
+    fn get_constant_solution(&self, steps: &mut Vec<Step>) -> SolutionSet {
+        let character = &self.quadratic.character;
+        let holds = self.sign.holds(self.quadratic.c.signum());
+        steps.push(Step::Constant {
+            c: self.quadratic.c,
+            sign: self.sign.clone(),
+            holds,
+        });
+        if holds {
+            SolutionSet::all(character)
         } else {
-            self.sign.reverse()
+            SolutionSet::empty(character)
+        }
+    }
+    /// the same inequality with a positive leading coefficient, where `leading` is
+    /// the coefficient to look at.
+    fn normalized(
+        &self,
+        leading: Number,
+        steps: &mut Vec<Step>,
+    ) -> Result<(Quadratic, Sign), ErrorKind> {
+        if leading.signum() == Ordering::Greater {
+            return Ok((self.quadratic.clone(), self.sign.clone()));
+        }
+        let (quadratic, sign) = (self.quadratic.clone().reverse()?, self.sign.reverse());
+        steps.push(Step::FlipSign {
+            quadratic: quadratic.clone(),
+            sign: sign.clone(),
+        });
+        Ok((quadratic, sign))
+    }
+    /// `bx + c sign 0` with b ≠ 0.
+    fn get_linear_solution(&self, steps: &mut Vec<Step>) -> Result<SolutionSet, ErrorKind> {
+        let (quadratic, sign) = self.normalized(self.quadratic.b, steps)?;
+        let root = Real::from(
+            quadratic
+                .c
+                .checked_neg()?
+                .checked_div(quadratic.b)?
+                .to_surd(),
+        );
+        let interval = match sign {
+            Sign::Lt => Interval::new(Bound::Unbounded, Bound::Open(root)),
+            Sign::Lte => Interval::new(Bound::Unbounded, Bound::Closed(root)),
+            Sign::Gt => Interval::new(Bound::Open(root), Bound::Unbounded),
+            Sign::Gte => Interval::new(Bound::Closed(root), Bound::Unbounded),
         };
-        let character = &self.quadratic.character;
-        match d.cmp(&0) {
-            Ordering::Less => match sign {
-                Sign::Lt | Sign::Lte => "no solution".to_string(),
-                Sign::Gt | Sign::Gte => "all real number".to_string(),
-            },
-            Ordering::Greater => match sign {
-                Sign::Lt => format!("{} < {} < {}", s1, character, s2),
-                Sign::Lte => format!("{} ≤ {} ≤ {}", s1, character, s2),
-                Sign::Gt => format!("{} < {} OR {} > {}", character, s1, character, s2),
-                Sign::Gte => format!("{} ≤ {} OR {} ≥ {}", character, s1, character, s2),
-            },
-            Ordering::Equal => match sign {
-                Sign::Lt => "no solution".to_string(),
-                Sign::Lte => format!("{} = {}", character, s1),
-                Sign::Gt => format!("all real number with {} ≠ {}", character, s1),
-                Sign::Gte => "all real number".to_string(),
-            },
+        Ok(SolutionSet::from_intervals(
+            &quadratic.character,
+            vec![interval],
+        ))
+    }
+    fn get_quadratic_solution(&self, steps: &mut Vec<Step>) -> Result<SolutionSet, ErrorKind> {
+        let (quadratic, sign) = self.normalized(self.quadratic.a, steps)?;
+        steps.push(Step::Discriminant {
+            a: quadratic.a,
+            b: quadratic.b,
+            c: quadratic.c,
+            d: quadratic.discriminant()?,
+        });
+        let character = &quadratic.character;
+        let roots = quadratic.roots()?;
+        steps.push(Step::Roots {
+            character: character.clone(),
+            roots: roots.clone(),
+        });
+        let (s1, s2) = match *roots.as_slice() {
+            [s1, s2] => {
+                steps.push(Step::ChooseIntervals {
+                    sign: sign.clone(),
+                    region: match sign {
+                        Sign::Lt | Sign::Lte => Region::Between,
+                        Sign::Gt | Sign::Gte => Region::Outside,
+                    },
+                    inclusive: sign.is_inclusive(),
+                });
+                (s1, s2)
+            }
+            [root] => {
+                steps.push(Step::NeverNegative {
+                    sign: sign.clone(),
+                    double_root: Some(root),
+                });
+                (root, root)
+            }
+            _ => {
+                steps.push(Step::NeverNegative {
+                    sign: sign.clone(),
+                    double_root: None,
+                });
+                return Ok(match sign {
+                    Sign::Lt | Sign::Lte => SolutionSet::empty(character),
+                    Sign::Gt | Sign::Gte => SolutionSet::all(character),
+                });
+            }
+        };
+        let (s1, s2) = (Real::from(s1), Real::from(s2));
+        // a double root needs no special case: the intervals collapse or merge on their own
+        Ok(match sign {
+            Sign::Lt => SolutionSet::from_intervals(
+                character,
+                vec![Interval::new(Bound::Open(s1), Bound::Open(s2))],
+            ),
+            Sign::Lte => SolutionSet::from_intervals(
+                character,
+                vec![Interval::new(Bound::Closed(s1), Bound::Closed(s2))],
+            ),
+            Sign::Gt => SolutionSet::from_intervals(
+                character,
+                vec![
+                    Interval::new(Bound::Unbounded, Bound::Open(s1)),
+                    Interval::new(Bound::Open(s2), Bound::Unbounded),
+                ],
+            ),
+            Sign::Gte => SolutionSet::from_intervals(
+                character,
+                vec![
+                    Interval::new(Bound::Unbounded, Bound::Closed(s1)),
+                    Interval::new(Bound::Closed(s2), Bound::Unbounded),
+                ],
+            ),
+        })
+    }
+}
+
+impl QuadraticInequality {
+    /// the normalized inequality written with `symbols`: `x² + 3x - 10 ≤ 0`, or
+    /// `x^2 + 3x - 10 <= 0` in ASCII.
+    pub fn pretty(&self, symbols: Symbols) -> Pretty<'_, Self> {
+        Pretty {
+            value: self,
+            symbols,
+        }
+    }
+}
+
+/// the normalized form `ax^2 + bx + c < 0`.
+impl fmt::Display for QuadraticInequality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} 0", self.quadratic, self.sign)
+    }
+}
+
+/// a polynomial or an inequality written with the given symbols; see
+/// [`Quadratic::pretty`], [`Polynomial::pretty`] and [`QuadraticInequality::pretty`].
+pub struct Pretty<'a, T> {
+    pub(crate) value: &'a T,
+    pub(crate) symbols: Symbols,
+}
+impl fmt::Display for Pretty<'_, Polynomial> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let polynomial = self.value;
+        let superscript = self.symbols == Symbols::Unicode;
+        fmt_terms(f, &polynomial.character, polynomial.terms(), superscript)
+    }
+}
+impl fmt::Display for Pretty<'_, Quadratic> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt_with(f, self.symbols == Symbols::Unicode)
+    }
+}
+impl fmt::Display for Pretty<'_, QuadraticInequality> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inequality = self.value;
+        write!(f, "{} ", inequality.quadratic.pretty(self.symbols))?;
+        match self.symbols {
+            Symbols::Unicode => write!(f, "{}", inequality.sign)?,
+            Symbols::Ascii => write!(f, "{}", inequality.sign.ascii())?,
         }
+        write!(f, " 0")
     }
 }
 
@@ -214,103 +950,148 @@ mod tests {
     #[test]
     fn new_quadratic() {
         assert_eq!(
-            Quadratic::from_monomials(vec![
-                Monomial {
-                    coefficient: Number(1),
-                    character: Some("x"),
-                    degree: Number(2)
-                },
-                Monomial {
-                    coefficient: Number(5),
-                    character: Some("x"),
-                    degree: Number(1)
-                },
-                Monomial {
-                    coefficient: Number(4),
-                    character: None,
-                    degree: Number(1)
-                },
-                Monomial {
-                    coefficient: Number(3),
-                    character: Some("x"),
-                    degree: Number(1)
-                },
-            ]),
+            Quadratic::from_polynomial(Polynomial {
+                character: "x".to_string(),
+                coefficients: vec![Number::from(4), Number::from(8), Number::from(1)],
+            }),
             Ok(Quadratic {
                 character: "x".to_string(),
-                a: 1,
-                b: 8,
-                c: 4,
+                a: Number::from(1),
+                b: Number::from(8),
+                c: Number::from(4),
             })
         );
     }
     #[test]
     fn new_quadratic_2() {
         assert_eq!(
-            Quadratic::from_monomials(vec![
-                Monomial {
-                    coefficient: Number(1),
-                    character: Some("x"),
-                    degree: Number(2)
-                },
-                Monomial {
-                    coefficient: Number(5),
-                    character: Some("x"),
-                    degree: Number(1)
-                },
-                Monomial {
-                    coefficient: Number(4),
-                    character: None,
-                    degree: Number(1)
-                },
-            ]),
+            Quadratic::from_polynomial(Polynomial {
+                character: "x".to_string(),
+                coefficients: vec![
+                    Number::from(4),
+                    Number::from(5),
+                    Number::from(1),
+                    Number::from(0),
+                    Number::from(0)
+                ],
+            }),
             Ok(Quadratic {
                 character: "x".to_string(),
-                a: 1,
-                b: 5,
-                c: 4,
+                a: Number::from(1),
+                b: Number::from(5),
+                c: Number::from(4),
             })
         );
     }
 
     #[test]
-    fn new_quadratic_wrong_character() {
+    fn new_quadratic_too_high_degree() {
         assert_eq!(
-            Quadratic::from_monomials(vec![
-                Monomial {
-                    coefficient: Number(1),
-                    character: Some("x"),
-                    degree: Number(2),
-                },
-                Monomial {
-                    coefficient: Number(5),
-                    character: Some("y"),
-                    degree: Number(1),
-                },
-                Monomial {
-                    coefficient: Number(4),
-                    character: None,
-                    degree: Number(0),
-                },
-            ]),
-            Err(Error::InvalidCharacter {
+            Quadratic::from_polynomial(Polynomial {
+                character: "x".to_string(),
+                coefficients: vec![
+                    Number::from(0),
+                    Number::from(0),
+                    Number::from(0),
+                    Number::from(1)
+                ],
+            }),
+            Err(ErrorKind::InvalidQuadratic)
+        );
+    }
+
+    #[test]
+    fn add_polynomial_wrong_character() {
+        assert_eq!(
+            Polynomial::variable("x").add(Polynomial::variable("y")),
+            Err(ErrorKind::InvalidCharacter {
                 expected: "x".to_string(),
                 found: "y".to_string()
             })
         );
     }
 
+    #[test]
+    fn power_of_polynomial() {
+        assert_eq!(
+            Polynomial::variable("x").pow(3),
+            Ok(Polynomial::new("x", vec![0, 0, 0, 1]))
+        );
+        assert_eq!(
+            Polynomial::variable("x").pow(u32::MAX),
+            Err(ErrorKind::DegreeTooHigh)
+        );
+        assert_eq!(
+            Polynomial::constant(Number::from(1)).pow(u32::MAX),
+            Ok(Polynomial::new("", vec![1]))
+        );
+    }
+
+    #[test]
+    fn expand_polynomial() {
+        // (x - 2)(x + 3) = x^2 + x - 6
+        let x_minus_2 = Polynomial::variable("x")
+            .add(Polynomial::constant(Number::from(-2)))
+            .unwrap();
+        let x_plus_3 = Polynomial::variable("x")
+            .add(Polynomial::constant(Number::from(3)))
+            .unwrap();
+        assert_eq!(
+            x_minus_2.mul(x_plus_3),
+            Ok(Polynomial {
+                character: "x".to_string(),
+                coefficients: vec![Number::from(-6), Number::from(1), Number::from(1)],
+            })
+        );
+        // 2(x + 1)^2 = 2x^2 + 4x + 2
+        let x_plus_1 = Polynomial::variable("x")
+            .add(Polynomial::constant(Number::from(1)))
+            .unwrap();
+        assert_eq!(
+            Polynomial::constant(Number::from(2)).mul(x_plus_1.pow(2).unwrap()),
+            Ok(Polynomial {
+                character: "x".to_string(),
+                coefficients: vec![Number::from(2), Number::from(4), Number::from(2)],
+            })
+        );
+    }
+
+    #[test]
+    fn gcd_of_polynomials() {
+        // (x - 1)(x + 2) and 2(x - 1)(x - 3)
+        assert_eq!(
+            Polynomial::new("x", vec![-2, 1, 1]).gcd(&Polynomial::new("x", vec![6, -8, 2])),
+            Ok(Polynomial::new("x", vec![-1, 1]))
+        );
+        // (x - 1/3)(x - 2/7)(x^3 - 2) and its derivative have no common factor
+        let polynomial = Polynomial::from_coefficients(
+            "x",
+            vec![
+                Number::new(-4, 21),
+                Number::new(26, 21),
+                Number::from(-2),
+                Number::new(2, 21),
+                Number::new(-13, 21),
+                Number::from(1),
+            ],
+        );
+        assert_eq!(
+            polynomial.gcd(&polynomial.derivative().unwrap()),
+            Ok(Polynomial::new("x", vec![1]))
+        );
+    }
+
     #[test]
     fn get_solution_of_quadratic() {
         assert_eq!(
             Quadratic {
                 character: "x".to_string(),
-                a: 1,
-                b: 5,
-                c: 4,
+                a: Number::from(1),
+                b: Number::from(5),
+                c: Number::from(4),
             }
             .get_solution(),
-            (-4.0, -1.0)
+            Ok(Some((Surd::integer(-4), Surd::integer(-1))))
         );
     }
 
@@ -320,27 +1101,115 @@ mod tests {
             QuadraticInequality::from_expr((
                 Quadratic {
                     character: "x".to_string(),
-                    a: 1,
-                    b: 5,
-                    c: 2,
+                    a: Number::from(1),
+                    b: Number::from(5),
+                    c: Number::from(2),
                 },
                 Sign::Lt,
                 Quadratic {
                     character: "x".to_string(),
-                    a: -1,
-                    b: 0,
-                    c: -2,
+                    a: Number::from(-1),
+                    b: Number::from(0),
+                    c: Number::from(-2),
                 }
             )),
-            QuadraticInequality {
+            Ok(QuadraticInequality {
                 quadratic: Quadratic {
                     character: "x".to_string(),
-                    a: 2,
-                    b: 5,
-                    c: 4,
+                    a: Number::from(2),
+                    b: Number::from(5),
+                    c: Number::from(4),
                 },
                 sign: Sign::Lt,
-            }
+            })
+        );
+    }
+
+    #[test]
+    fn display_quadratic_inequality() {
+        let quad_ineq = |a, b, c, sign| {
+            QuadraticInequality::new(Quadratic::new("x".to_string(), a, b, c), sign)
+        };
+        assert_eq!(
+            quad_ineq(2, 5, 4, Sign::Gt).to_string(),
+            "2x^2 + 5x + 4 > 0"
+        );
+        assert_eq!(quad_ineq(-1, 0, -1, Sign::Lte).to_string(), "-x^2 - 1 ≤ 0");
+        assert_eq!(quad_ineq(0, 0, 0, Sign::Lt).to_string(), "0 < 0");
+        assert_eq!(
+            quad_ineq(1, -3, 0, Sign::Gte)
+                .pretty(Symbols::Unicode)
+                .to_string(),
+            "x² - 3x ≥ 0"
+        );
+        assert_eq!(
+            quad_ineq(1, -3, 0, Sign::Gte)
+                .pretty(Symbols::Ascii)
+                .to_string(),
+            "x^2 - 3x >= 0"
+        );
+    }
+
+    #[test]
+    fn solve_from_sides() {
+        // 2x^2 + 5x ≥ -x^2 - 2
+        let quad_ineq = QuadraticInequality::from_sides(
+            Quadratic::new("x", 2, 5, 0),
+            Sign::Gte,
+            Quadratic::new("x", -1, 0, -2),
+        )
+        .unwrap();
+        assert_eq!(quad_ineq.quadratic().a(), Number::from(3));
+        assert_eq!(quad_ineq.quadratic().c(), Number::from(2));
+        assert_eq!(quad_ineq.solve().unwrap().to_string(), "x ≤ -1 OR x ≥ -2/3");
+        let steps = quad_ineq.explain().unwrap();
+        assert!(matches!(
+            steps.steps().first(),
+            Some(Step::CollectTerms { .. })
+        ));
+    }
+
+    #[test]
+    fn new_quadratic_inequality_wrong_character() {
+        assert_eq!(
+            QuadraticInequality::from_expr((
+                Quadratic {
+                    character: "x".to_string(),
+                    a: Number::from(1),
+                    b: Number::from(0),
+                    c: Number::from(0),
+                },
+                Sign::Gt,
+                Quadratic {
+                    character: "y".to_string(),
+                    a: Number::from(0),
+                    b: Number::from(1),
+                    c: Number::from(0),
+                }
+            )),
+            Err(ErrorKind::InvalidCharacter {
+                expected: "x".to_string(),
+                found: "y".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn checked_number_arithmetic() {
+        let max = Number::from(i64::MAX);
+        assert_eq!(max.checked_add(Number::from(1)), Err(ErrorKind::Overflow));
+        assert_eq!(max.checked_mul(max), Err(ErrorKind::Overflow));
+        assert_eq!(
+            Number::from(i64::MIN).checked_neg(),
+            Err(ErrorKind::Overflow)
+        );
+        assert_eq!(
+            Number::from(1).checked_div(Number::from(0)),
+            Err(ErrorKind::DivisionByZero)
+        );
+        assert_eq!(
+            Number::new(i64::MAX, 2).checked_mul(Number::from(2)),
+            Ok(max)
         );
     }
 
@@ -348,25 +1217,25 @@ mod tests {
     fn add_quadratic() {
         let left = Quadratic {
             character: "x".to_string(),
-            a: 1,
-            b: 3,
-            c: 2,
+            a: Number::from(1),
+            b: Number::from(3),
+            c: Number::from(2),
         };
 
         let right = Quadratic {
             character: "x".to_string(),
-            a: 3,
-            b: 1,
-            c: -3,
+            a: Number::from(3),
+            b: Number::from(1),
+            c: Number::from(-3),
         };
         let result = Quadratic {
             character: "x".to_string(),
-            a: 4,
-            b: 4,
-            c: -1,
+            a: Number::from(4),
+            b: Number::from(4),
+            c: Number::from(-1),
         };
 
-        assert_eq!(left + right, result);
+        assert_eq!(left.add(right), Ok(result));
     }
 
     #[test]
@@ -375,14 +1244,16 @@ mod tests {
             QuadraticInequality {
                 quadratic: Quadratic {
                     character: "x".to_string(),
-                    a: 1,
-                    b: 5,
-                    c: 4,
+                    a: Number::from(1),
+                    b: Number::from(5),
+                    c: Number::from(4),
                 },
                 sign: Sign::Lt,
             }
-            .get_solution(),
-            "-4 < x < -1".to_string()
+            .solve()
+            .unwrap()
+            .to_string(),
+            "-4 < x < -1"
         );
     }
 
@@ -392,14 +1263,16 @@ mod tests {
             QuadraticInequality {
                 quadratic: Quadratic {
                     character: "x".to_string(),
-                    a: -1,
-                    b: 5,
-                    c: -4,
+                    a: Number::from(-1),
+                    b: Number::from(5),
+                    c: Number::from(-4),
                 },
                 sign: Sign::Lt,
             }
-            .get_solution(),
-            "x < 1 OR x > 4".to_string()
+            .solve()
+            .unwrap()
+            .to_string(),
+            "x < 1 OR x > 4"
         );
     }
 
@@ -409,66 +1282,144 @@ mod tests {
             QuadraticInequality {
                 quadratic: Quadratic {
                     character: "x".to_string(),
-                    a: 1,
-                    b: 4,
-                    c: 4,
+                    a: Number::from(1),
+                    b: Number::from(4),
+                    c: Number::from(4),
                 },
                 sign: Sign::Lte,
             }
-            .get_solution(),
-            "x = -2".to_string()
+            .solve()
+            .unwrap()
+            .to_string(),
+            "x = -2"
         );
         assert_eq!(
             QuadraticInequality {
                 quadratic: Quadratic {
                     character: "x".to_string(),
-                    a: 1,
-                    b: 4,
-                    c: 4,
+                    a: Number::from(1),
+                    b: Number::from(4),
+                    c: Number::from(4),
                 },
                 sign: Sign::Gte,
             }
-            .get_solution(),
-            "all real number".to_string()
+            .solve()
+            .unwrap()
+            .to_string(),
+            "all real number"
         );
         assert_eq!(
             QuadraticInequality {
                 quadratic: Quadratic {
                     character: "x".to_string(),
-                    a: 1,
-                    b: 4,
-                    c: 5,
+                    a: Number::from(1),
+                    b: Number::from(4),
+                    c: Number::from(5),
                 },
                 sign: Sign::Lt,
             }
-            .get_solution(),
-            "no solution".to_string()
+            .solve()
+            .unwrap()
+            .to_string(),
+            "no solution"
         );
         assert_eq!(
             QuadraticInequality {
                 quadratic: Quadratic {
                     character: "x".to_string(),
-                    a: 1,
-                    b: 4,
-                    c: 5,
+                    a: Number::from(1),
+                    b: Number::from(4),
+                    c: Number::from(5),
                 },
                 sign: Sign::Gt,
             }
-            .get_solution(),
-            "all real number".to_string()
+            .solve()
+            .unwrap()
+            .to_string(),
+            "all real number"
         );
         assert_eq!(
             QuadraticInequality {
                 quadratic: Quadratic {
                     character: "x".to_string(),
-                    a: 1,
-                    b: 4,
-                    c: 5,
+                    a: Number::from(1),
+                    b: Number::from(4),
+                    c: Number::from(5),
                 },
                 sign: Sign::Lte,
             }
-            .get_solution(),
-            "no solution".to_string()
+            .solve()
+            .unwrap()
+            .to_string(),
+            "no solution"
+        );
+    }
+
+    #[test]
+    fn get_linear_solution() {
+        assert_eq!(
+            QuadraticInequality {
+                quadratic: Quadratic {
+                    character: "x".to_string(),
+                    a: Number::from(0),
+                    b: Number::from(3),
+                    c: Number::from(6),
+                },
+                sign: Sign::Gt,
+            }
+            .solve()
+            .unwrap()
+            .to_string(),
+            "x > -2"
+        );
+        assert_eq!(
+            QuadraticInequality {
+                quadratic: Quadratic {
+                    character: "x".to_string(),
+                    a: Number::from(0),
+                    b: Number::from(-2),
+                    c: Number::from(1),
+                },
+                sign: Sign::Gte,
+            }
+            .solve()
+            .unwrap()
+            .to_string(),
+            "x ≤ 1/2"
+        );
+    }
+
+    #[test]
+    fn get_constant_solution() {
+        assert_eq!(
+            QuadraticInequality {
+                quadratic: Quadratic {
+                    character: "x".to_string(),
+                    a: Number::from(0),
+                    b: Number::from(0),
+                    c: Number::from(6),
+                },
+                sign: Sign::Gt,
+            }
+            .solve()
+            .unwrap()
+            .to_string(),
+            "all real number"
+        );
+        assert_eq!(
+            QuadraticInequality {
+                quadratic: Quadratic {
+                    character: "x".to_string(),
+                    a: Number::from(0),
+                    b: Number::from(0),
+                    c: Number::from(0),
+                },
+                sign: Sign::Lt,
+            }
+            .solve()
+            .unwrap()
+            .to_string(),
+            "no solution"
         );
     }
 }
@@ -0,0 +1,144 @@
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    chained::ChainedInequality,
+    error::{Error, Span},
+    locale::{Locale, Localize},
+    mathml, parse_and_solve,
+    solution::SolutionSet,
+};
+
+/// what went wrong, for the web page.
+#[derive(Debug, PartialEq, Clone)]
+struct Failure {
+    kind: &'static str,
+    message: String,
+    /// the span in UTF-16 code units, which is how JavaScript indexes strings.
+    start: usize,
+    end: usize,
+}
+
+/// the answer to one input, or what went wrong with it and where, as handed to
+/// JavaScript.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+#[derive(Debug, PartialEq, Clone)]
+pub struct SolveResult {
+    answer: Option<String>,
+    mathml: Option<String>,
+    failure: Option<Failure>,
+}
+
+/// the offset of byte `i` of `input` in UTF-16 code units. `i` is clamped to
+/// `input`, since a span may come from elsewhere.
+fn utf16_offset(input: &str, i: usize) -> usize {
+    let mut i = i.min(input.len());
+    while !input.is_char_boundary(i) {
+        i -= 1;
+    }
+    input[..i].encode_utf16().count()
+}
+
+impl SolveResult {
+    pub(crate) fn new(
+        input: &str,
+        result: Result<(ChainedInequality, SolutionSet), Error>,
+        locale: Locale,
+    ) -> Self {
+        match result {
+            Ok((inequality, solution)) => Self {
+                answer: Some(solution.localized(locale).to_string()),
+                mathml: Some(mathml::problem_and_answer(&inequality, &solution)),
+                failure: None,
+            },
+            Err(e) => {
+                let Span { start, end } = e.span();
+                Self {
+                    answer: None,
+                    mathml: None,
+                    failure: Some(Failure {
+                        kind: e.kind().code(),
+                        message: e.localized(locale).to_string(),
+                        start: utf16_offset(input, start),
+                        end: utf16_offset(input, end),
+                    }),
+                }
+            }
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+impl SolveResult {
+    /// whether the input was solved.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
+    pub fn ok(&self) -> bool {
+        self.answer.is_some()
+    }
+    /// the answer, if the input was solved.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
+    pub fn answer(&self) -> Option<String> {
+        self.answer.clone()
+    }
+    /// the normalized inequality and its solution as a MathML `<math>` element, if
+    /// the input was solved.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
+    pub fn mathml(&self) -> Option<String> {
+        self.mathml.clone()
+    }
+    /// what kind of error it was, such as `syntax`; see [`crate::error::ErrorKind::code`].
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
+    pub fn kind(&self) -> Option<String> {
+        self.failure
+            .as_ref()
+            .map(|failure| failure.kind.to_string())
+    }
+    /// what went wrong, in the language asked for.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
+    pub fn message(&self) -> Option<String> {
+        self.failure.as_ref().map(|failure| failure.message.clone())
+    }
+    /// where the part of the input that went wrong starts, in UTF-16 code units.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
+    pub fn start(&self) -> Option<usize> {
+        self.failure.as_ref().map(|failure| failure.start)
+    }
+    /// where the part of the input that went wrong ends, in UTF-16 code units.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
+    pub fn end(&self) -> Option<usize> {
+        self.failure.as_ref().map(|failure| failure.end)
+    }
+}
+
+/// solve `input` with the answer or the error written in the language tagged `lang`
+/// (`en` or `ko`). no tag, or an unknown one, means English.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn solve(input: &str, lang: Option<String>) -> SolveResult {
+    let locale = lang.and_then(|lang| lang.parse().ok()).unwrap_or_default();
+    SolveResult::new(input, parse_and_solve(input), locale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_for_the_web() {
+        let result = solve("x^2-1<0", None);
+        assert!(result.ok());
+        assert_eq!(result.answer(), Some("-1 < x < 1".to_string()));
+        assert_eq!(result.mathml(), Some(crate::solve_mathml("x^2-1<0", "en")));
+        assert_eq!(result.kind(), None);
+
+        // `≥` is one UTF-16 code unit but three bytes
+        let result = solve("x^2≥1)2", Some("ko".to_string()));
+        assert!(!result.ok());
+        assert_eq!(result.kind(), Some("trailing_input".to_string()));
+        assert_eq!(
+            result.message(),
+            Some("예상하지 못한 입력 ')2'".to_string())
+        );
+        assert_eq!((result.start(), result.end()), (Some(5), Some(7)));
+        assert_eq!(result.mathml(), None);
+    }
+}
@@ -0,0 +1,235 @@
+use std::{cmp::Ordering, convert::TryFrom};
+
+/// an integer of any size, for the exact arithmetic that does not fit in 128 bits:
+/// comparing surds squares their parts twice over, and the remainders of a
+/// polynomial gcd grow with the degree.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) struct Wide {
+    negative: bool,
+    /// the magnitude in base 2³², least significant digit first, with no
+    /// leading zeros, so zero has no digits.
+    digits: Vec<u32>,
+}
+impl Wide {
+    fn from_digits(negative: bool, mut digits: Vec<u32>) -> Self {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        Self {
+            negative: negative && !digits.is_empty(),
+            digits,
+        }
+    }
+    pub(crate) fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+    pub(crate) fn signum(&self) -> Ordering {
+        match (self.is_zero(), self.negative) {
+            (true, _) => Ordering::Equal,
+            (false, true) => Ordering::Less,
+            (false, false) => Ordering::Greater,
+        }
+    }
+    pub(crate) fn neg(&self) -> Self {
+        Self::from_digits(!self.negative, self.digits.clone())
+    }
+    pub(crate) fn abs(&self) -> Self {
+        Self::from_digits(false, self.digits.clone())
+    }
+    pub(crate) fn add(&self, other: &Self) -> Self {
+        if self.negative == other.negative {
+            return Self::from_digits(self.negative, add_digits(&self.digits, &other.digits));
+        }
+        match compare_digits(&self.digits, &other.digits) {
+            Ordering::Less => {
+                Self::from_digits(other.negative, sub_digits(&other.digits, &self.digits))
+            }
+            _ => Self::from_digits(self.negative, sub_digits(&self.digits, &other.digits)),
+        }
+    }
+    pub(crate) fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+    pub(crate) fn mul(&self, other: &Self) -> Self {
+        let mut digits = vec![0; self.digits.len() + other.digits.len()];
+        for (i, &x) in self.digits.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &y) in other.digits.iter().enumerate() {
+                let t = u64::from(x) * u64::from(y) + u64::from(digits[i + j]) + carry;
+                digits[i + j] = t as u32;
+                carry = t >> 32;
+            }
+            digits[i + other.digits.len()] = carry as u32;
+        }
+        Self::from_digits(self.negative != other.negative, digits)
+    }
+    pub(crate) fn pow(&self, exponent: usize) -> Self {
+        (0..exponent).fold(Self::from(1), |power, _| power.mul(self))
+    }
+    /// the quotient rounded toward zero and the remainder, which takes the sign of
+    /// `self`, as for the primitive integers. `other` must not be zero.
+    pub(crate) fn div_rem(&self, other: &Self) -> (Self, Self) {
+        assert!(!other.is_zero(), "division by zero");
+        let mut quotient = vec![0; self.digits.len()];
+        let mut remainder = Vec::new();
+        // long division in base 2, one bit of `self` at a time
+        for i in (0..self.digits.len() * 32).rev() {
+            remainder = shift_in(&remainder, self.digits[i / 32] >> (i % 32) & 1);
+            if compare_digits(&remainder, &other.digits) != Ordering::Less {
+                remainder = trimmed(sub_digits(&remainder, &other.digits));
+                quotient[i / 32] |= 1 << (i % 32);
+            }
+        }
+        (
+            Self::from_digits(self.negative != other.negative, quotient),
+            Self::from_digits(self.negative, remainder),
+        )
+    }
+    /// the greatest common divisor, never negative. `gcd(0, 0)` is 0.
+    pub(crate) fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while !b.is_zero() {
+            let remainder = a.div_rem(&b).1;
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+}
+impl From<i64> for Wide {
+    fn from(n: i64) -> Self {
+        let magnitude = n.unsigned_abs();
+        Self::from_digits(n < 0, vec![magnitude as u32, (magnitude >> 32) as u32])
+    }
+}
+impl TryFrom<&Wide> for i64 {
+    type Error = ();
+    fn try_from(n: &Wide) -> Result<Self, ()> {
+        if n.digits.len() > 2 {
+            return Err(());
+        }
+        let magnitude = n
+            .digits
+            .iter()
+            .rev()
+            .fold(0, |m, &d| m << 32 | u64::from(d));
+        match n.negative {
+            true => 0i64.checked_sub_unsigned(magnitude).ok_or(()),
+            false => i64::try_from(magnitude).map_err(|_| ()),
+        }
+    }
+}
+impl Ord for Wide {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_digits(&self.digits, &other.digits),
+            (true, true) => compare_digits(&other.digits, &self.digits),
+        }
+    }
+}
+impl PartialOrd for Wide {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn trimmed(mut digits: Vec<u32>) -> Vec<u32> {
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    digits
+}
+
+/// `digits` doubled, plus `bit`.
+fn shift_in(digits: &[u32], bit: u32) -> Vec<u32> {
+    let mut shifted = Vec::with_capacity(digits.len() + 1);
+    let mut carry = bit;
+    for &d in digits {
+        shifted.push(d << 1 | carry);
+        carry = d >> 31;
+    }
+    shifted.push(carry);
+    trimmed(shifted)
+}
+
+/// compare two magnitudes without leading zeros.
+fn compare_digits(x: &[u32], y: &[u32]) -> Ordering {
+    x.len()
+        .cmp(&y.len())
+        .then_with(|| x.iter().rev().cmp(y.iter().rev()))
+}
+
+fn add_digits(x: &[u32], y: &[u32]) -> Vec<u32> {
+    let (long, short) = if x.len() >= y.len() { (x, y) } else { (y, x) };
+    let mut digits = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0;
+    for (i, &d) in long.iter().enumerate() {
+        let t = u64::from(d) + u64::from(short.get(i).copied().unwrap_or(0)) + carry;
+        digits.push(t as u32);
+        carry = t >> 32;
+    }
+    digits.push(carry as u32);
+    digits
+}
+
+/// x - y, where x is at least y.
+fn sub_digits(x: &[u32], y: &[u32]) -> Vec<u32> {
+    let mut digits = Vec::with_capacity(x.len());
+    let mut borrow = 0;
+    for (i, &d) in x.iter().enumerate() {
+        let (t, under) = d.overflowing_sub(y.get(i).copied().unwrap_or(0));
+        let (t, under_again) = t.overflowing_sub(borrow);
+        digits.push(t);
+        borrow = u32::from(under || under_again);
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let big = Wide::from(i64::MAX);
+        let square = big.mul(&big);
+        assert_eq!(square.sub(&square), Wide::from(0));
+        assert_eq!(square.add(&big.neg()).add(&big), square);
+        assert_eq!(big.neg().pow(3).signum(), Ordering::Less);
+        assert!(square > big && big.neg() > square.neg());
+    }
+
+    #[test]
+    fn division() {
+        let big = Wide::from(i64::MAX);
+        let n = big.pow(3).add(&Wide::from(5));
+        assert_eq!(n.div_rem(&big.pow(2)), (big.clone(), Wide::from(5)));
+        assert_eq!(
+            Wide::from(-7).div_rem(&Wide::from(2)),
+            (Wide::from(-3), Wide::from(-1))
+        );
+        assert_eq!(
+            big.pow(2)
+                .mul(&Wide::from(6))
+                .gcd(&big.mul(&Wide::from(-4))),
+            big.mul(&Wide::from(2))
+        );
+    }
+
+    #[test]
+    fn convert_to_i64() {
+        for n in [0, 1, -1, i64::MAX, i64::MIN] {
+            assert_eq!(i64::try_from(&Wide::from(n)), Ok(n));
+        }
+        assert_eq!(
+            i64::try_from(&Wide::from(i64::MIN).sub(&Wide::from(1))),
+            Err(())
+        );
+        assert_eq!(
+            i64::try_from(&Wide::from(i64::MAX).add(&Wide::from(1))),
+            Err(())
+        );
+    }
+}
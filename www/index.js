@@ -5,5 +5,17 @@ const run_btn = document.getElementById('run');
 const result_div = document.getElementById('result_field');
 
 run_btn.addEventListener('click', e => {
-  result_div.textContent = wasm.solve(input.value);
-})
\ No newline at end of file
+  const result = wasm.solve(input.value, 'ko');
+  if (result.ok) {
+    // the markup is built and escaped by the wasm module
+    result_div.innerHTML = result.mathml;
+    input.classList.remove('error');
+  } else {
+    result_div.textContent = result.message;
+    // select the part of the input that went wrong, at least one character of it
+    input.classList.add('error');
+    input.focus();
+    input.setSelectionRange(result.start, Math.max(result.end, result.start + 1));
+  }
+  result.free();
+})